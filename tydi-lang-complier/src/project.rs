@@ -5,7 +5,7 @@ use std::sync::{Arc, RwLock};
 use crate::project_description::*;
 use tydi_lang_parser::tydi_memory_representation::*;
 use tydi_lang_json_generator::generate_json_representation_from_tydi_project;
-use tydi_lang_parser::post_compile::sugaring_auto_insertion_duplicator_voider;
+use tydi_lang_parser::post_compile::{sugaring_auto_insertion_duplicator_voider, check_clock_domain};
 
 pub struct TydiProject {
     name: String,
@@ -60,9 +60,16 @@ impl TydiProject {
     }
 
     pub fn evaluation(&self, package_name: String, target_name: String) -> Result<String, String> {
-        let result = self.project.read().unwrap().evaluate_target(package_name, target_name);
+        let evaluator = self.project.read().unwrap().new_evaluator();
+        let result = self.project.read().unwrap().evaluate_target_with_evaluator(package_name, target_name, evaluator.clone());
+        //messages from print/warn/error in the source code, they are shown even if the evaluation fails
+        print!("{}", evaluator.read().unwrap().print_diagnostics());
         match result {
-            Ok(evaluator) => {
+            Ok(_) => {
+                let check_result = check_clock_domain::check_project(self.project.clone(), evaluator.clone());
+                if check_result.is_err() {
+                    return Err(check_result.err().unwrap().print());
+                }
                 return Ok(evaluator.read().unwrap().print_evaluation_record());
             },
             Err(err) => {
@@ -1,5 +1,5 @@
 use std::sync::{Arc, RwLock};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use serde::Serialize;
 
@@ -48,6 +48,7 @@ pub struct JsonRepresentation {
     pub streamlets: BTreeMap<String, Arc<RwLock<Streamlet>>>,
     #[serde(with = "crate::serde_serialization::arc_rwlock_in_btree_map_value")]
     pub implementations: BTreeMap<String, Arc<RwLock<Implementation>>>,
+    pub clock_domains: BTreeSet<String>,
 
     pub compile_options: JsonRepresentation_compile_options,
 }
@@ -58,6 +59,7 @@ impl JsonRepresentation {
             logic_types: BTreeMap::new(),
             streamlets: BTreeMap::new(),
             implementations: BTreeMap::new(),
+            clock_domains: BTreeSet::new(),
             compile_options: JsonRepresentation_compile_options::new(),
         };
     }
@@ -66,6 +68,7 @@ impl JsonRepresentation {
         self.streamlets.append(&mut other.streamlets);
         self.implementations.append(&mut other.implementations);
         self.logic_types.append(&mut other.logic_types);
+        self.clock_domains.append(&mut other.clock_domains);
     }
 }
 
@@ -142,7 +142,7 @@ impl Implementation {
                 tydi_memory_representation::implementation::ImplementationType::TemplateInstance(template_impl, template_args) => {
                     let mut output_template_args = BTreeMap::new();
                     for (arg_index, arg) in template_args {
-                        let (value, mut dependencies) = Value::translate_from_tydi_project(tydi_project.clone(), arg)?;
+                        let (value, mut dependencies) = Value::translate_from_tydi_project(tydi_project.clone(), arg, format!("{}__template_arg{}", &target_var_name, arg_index))?;
                         output_dependency.append(&mut dependencies);
                         output_template_args.insert(*arg_index, value);
                     }
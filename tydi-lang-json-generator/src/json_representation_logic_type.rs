@@ -20,6 +20,7 @@ pub enum LogicType {
     Group(Arc<RwLock<LogicGroup>>),
     Union(Arc<RwLock<LogicUnion>>),
     Stream(Arc<RwLock<LogicStream>>),
+    Array(Arc<RwLock<LogicArray>>),
     Ref(RefInfo),
 }
 
@@ -153,6 +154,9 @@ impl serde::Serialize for LogicType {
             LogicType::Stream(v) => {
                 state.serialize_field("value", &*v.read().unwrap())?;
             },
+            LogicType::Array(v) => {
+                state.serialize_field("value", &*v.read().unwrap())?;
+            },
             LogicType::Ref(v) => {
                 state.serialize_field("value", &v.ref_name)?;
                 state.serialize_field("alias", &v.alias)?;
@@ -170,6 +174,13 @@ impl LogicType {
         let target_var_name = name_conversion::get_global_variable_name(target_var.clone());
         let var_value = target_var.read().unwrap().get_value();
 
+        //a logic type declared as an array, e.g. Bit(8)[4]
+        if target_var.read().unwrap().get_array_size().is_some() {
+            if let TypedValue::Array(array) = &var_value {
+                return LogicArray::translate_from_tydi_project(tydi_project.clone(), array, target_var_name);
+            }
+        }
+
         return Self::translate_from_tydi_project_type_value(tydi_project.clone(), &var_value, target_var_name, Some(target_var.clone()));
     }
 
@@ -372,6 +383,32 @@ impl LogicUnion {
 
 
 
+#[derive(Clone, Debug, Serialize)]
+pub struct LogicArray {
+    element_type: LogicType,
+    size: usize,
+}
+
+impl LogicArray {
+    pub fn translate_from_tydi_project(tydi_project: Arc<RwLock<Project>>, tydi_target: &Vec<TypedValue>, target_var_name: String) -> Result<(Vec<LogicType>, BTreeMap<String, Arc<RwLock<LogicType>>>), String> {
+        let mut output_dependency = BTreeMap::new();
+
+        //all elements of a logic type array share the same type
+        assert!(tydi_target.len() > 0);
+        let (element_type, mut dependencies) = LogicType::translate_from_tydi_project_type_value(tydi_project.clone(), &tydi_target[0], format!("{}_element", &target_var_name), None)?;
+        output_dependency.append(&mut dependencies);
+        assert!(element_type.len() == 1, "the element of a logic type array should be a single logic type, not an array");
+
+        let output_array = Self {
+            element_type: element_type[0].clone(),
+            size: tydi_target.len(),
+        };
+        output_dependency.insert(target_var_name.clone(), Arc::new(RwLock::new(LogicType::Array(Arc::new(RwLock::new(output_array))))));
+
+        return Ok((vec![LogicType::Ref(RefInfo::new(target_var_name))], output_dependency));
+    }
+}
+
 #[derive(Clone, Debug, Serialize)]
 pub struct LogicStream {
     stream_type: LogicType,
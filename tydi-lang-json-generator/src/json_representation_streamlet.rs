@@ -9,6 +9,7 @@ use crate::json_representation_all::JsonRepresentation;
 use crate::name_conversion;
 use crate::util::{generate_init_name, GetName};
 use tydi_lang_parser::tydi_memory_representation::{self, Project, Scope, GlobalIdentifier, GetScope};
+use tydi_lang_parser::post_compile::check_clock_domain::get_port_clock_domain;
 
 
 #[derive(Clone, Debug, Serialize)]
@@ -24,6 +25,7 @@ pub struct Port {
     pub name: String,
     logic_type: LogicType,
     direction: PortDirection,
+    clock_domain: String,
     document: Option<String>,
 }
 
@@ -33,6 +35,7 @@ impl Port {
             name: generate_init_name(),
             logic_type: LogicType::Unknwon,
             direction: PortDirection::Unknown,
+            clock_domain: String::from(""),
             document: None,
         };
         return output;
@@ -59,8 +62,13 @@ impl Port {
         let result_logic_type = result_logic_type[0].clone();
         output_port.logic_type = result_logic_type;
         output_port.document = target_port.read().unwrap().get_document();
+        output_port.clock_domain = match get_port_clock_domain(target_port.clone()) {
+            Some(clock_domain) => clock_domain,
+            None => return Err(format!("the clock domain of port ({}) is not evaluated", target_port.read().unwrap().get_name())),
+        };
         let mut output_json_representation = JsonRepresentation::new();
         output_json_representation.logic_types.append(&mut dependencies);
+        output_json_representation.clock_domains.insert(output_port.clock_domain.clone());
         return Ok((output_port, output_json_representation));
     }
 }
@@ -130,6 +138,7 @@ impl Streamlet {
                         }
                     }
                 }
+                //the ports expanded from a port bundle are also variables of the streamlet, so they are translated as ordinary ports
                 _ => (),
             }
         }
@@ -3,7 +3,7 @@ use std::sync::{Arc, RwLock};
 use serde::Serialize;
 use tydi_lang_parser::tydi_memory_representation::{TypedValue, Project};
 
-use crate::{json_representation_logic_type::LogicType, json_representation_all::JsonRepresentation, util::generate_random_str};
+use crate::{json_representation_logic_type::LogicType, json_representation_all::JsonRepresentation};
 
 
 
@@ -21,7 +21,7 @@ pub enum Value {
 }
 
 impl Value {
-    pub fn translate_from_tydi_project(tydi_project: Arc<RwLock<Project>>, value: &TypedValue) -> Result<(Value, JsonRepresentation), String> {
+    pub fn translate_from_tydi_project(tydi_project: Arc<RwLock<Project>>, value: &TypedValue, default_name: String) -> Result<(Value, JsonRepresentation), String> {
         let mut output_dependency = JsonRepresentation::new();
 
         match &value {
@@ -31,7 +31,7 @@ impl Value {
             TypedValue::FloatValue(v) => return Ok((Value::Float(*v), output_dependency)),
             TypedValue::ClockDomainValue(v) => return Ok((Value::ClockDomain(v.clone()), output_dependency)),
             TypedValue::LogicTypeValue(_) => {
-                let (output_value, mut dependencies) = LogicType::translate_from_tydi_project_type_value(tydi_project, value, generate_random_str(8), None)?;
+                let (output_value, mut dependencies) = LogicType::translate_from_tydi_project_type_value(tydi_project, value, default_name.clone(), None)?;
                 output_dependency.logic_types.append(&mut dependencies);
                 if output_value.len() != 1 {
                     return Err(format!("the output logic type should not be a logic type array"));
@@ -40,7 +40,7 @@ impl Value {
                 return Ok((Value::LogicType(output_value), output_dependency));
             },
             TypedValue::RefToVar(var) => {
-                let (output_value, mut dependencies) = LogicType::translate_from_tydi_project_type_value(tydi_project, value, generate_random_str(8), Some(var.clone()))?;
+                let (output_value, mut dependencies) = LogicType::translate_from_tydi_project_type_value(tydi_project, value, default_name.clone(), Some(var.clone()))?;
                 output_dependency.logic_types.append(&mut dependencies);
                 if output_value.len() != 1 {
                     return Err(format!("the output logic type should not be a logic type array"));
@@ -2,8 +2,6 @@ use std::sync::{Arc, RwLock};
 
 use tydi_lang_parser::{tydi_memory_representation::{Variable, Scope, GlobalIdentifier}, trait_common::GetName};
 
-use crate::util;
-
 pub fn get_global_variable_name(var: Arc<RwLock<Variable>>) -> String {
     let parent_scope = var.read().unwrap().get_parent_scope();
     let scope_part = match parent_scope {
@@ -11,7 +9,7 @@ pub fn get_global_variable_name(var: Arc<RwLock<Variable>>) -> String {
             scope.read().unwrap().get_name()
         },
         None => {
-            format!("{}", util::generate_random_str(4))
+            format!("predefined")    //predefined variables are not in any scope
         },
     };
     let variable_part = var.read().unwrap().get_name();
@@ -470,7 +470,7 @@ fn casper_wrong_error_line_bug() {
                 instance json_parser(schema_0_parser_0_top_com);
             
                 input => decompressor.co;
-                decompressor.de => json_parser.input;
+                decompressor.de => json_parser.input @NoTypeCheck;
             }
         
         "#);
@@ -1492,4 +1492,457 @@ fn casper_json_example() {
     let json_output = generate_json_representation_from_tydi_project(project.clone(), format!("top_impl"), format!("student_schema_parser")).expect("fail to generate json");
     std::fs::write("./json_output.json", &json_output).unwrap();
     println!("{}", json_output);
-}
\ No newline at end of file
+}
+
+#[test]
+fn multi_clock_domain() {
+    let project = Project::new(format!("sample_project"));
+    {
+        let mut project_write = project.write().unwrap();
+
+        let src_pack0 = String::from(r#"
+        package pack0;
+
+        bit8_stream = Stream(Bit(8));
+
+        streamlet bypass_s<clk: clock> {
+            port_in: bit8_stream in /clk;
+            port_out: bit8_stream out /clk;
+        }
+
+        impl bypass_i<clk: clock> of bypass_s<clk> {
+            self.port_in => self.port_out;
+        }
+
+        streamlet top_s {
+            port_in: bit8_stream in /"clk_a";
+            port_out: bit8_stream out /"clk_b";
+            port_default: bit8_stream out;
+        }
+
+        impl top_i of top_s {
+            instance inst_a(bypass_i<"clk_a">);
+            instance inst_b(bypass_i<"clk_b">);
+            self.port_in => inst_a.port_in;
+            inst_a.port_out => inst_b.port_in @ClockDomainCrossing;
+            inst_b.port_out => self.port_out;
+        }
+
+        "#);
+
+        let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+        if status.is_err() {
+            panic!("{}", status.err().unwrap().print());
+        }
+    }
+    project.read().unwrap().evaluate_target(format!("top_i"), format!("pack0")).expect("fail to evaluate");
+
+    let json_representation = crate::generate_json_target_from_tydi_project(project.clone(), format!("top_i"), format!("pack0")).expect("fail to generate json");
+    let clock_domains: Vec<String> = json_representation.clock_domains.iter().cloned().collect();
+    assert_eq!(clock_domains, vec![format!("clk_a"), format!("clk_b"), format!("default_time_domain")]);
+
+    let json_output = generate_json_representation_from_tydi_project(project.clone(), format!("top_i"), format!("pack0")).expect("fail to generate json");
+    assert!(json_output.contains("\"clock_domain\": \"clk_a\""));
+    println!("{}", json_output);
+}
+
+#[test]
+fn deterministic_names() {
+    let compile = || -> String {
+        let project = Project::new(format!("sample_project"));
+        {
+            let mut project_write = project.write().unwrap();
+
+            let src_pack0 = String::from(r#"
+            package pack;
+            use std;
+
+            bit8_stream = Stream(Bit(8));
+
+            streamlet test_s<N: int> {
+                in_port: bit8_stream in;
+                out_port: Stream(Bit(N)) out;
+            }
+
+            impl test_i<N: int> of test_s<N> @External {
+
+            }
+
+            test_i8 = test_i<8>;
+
+            streamlet top_s {
+                in_port: bit8_stream in;
+                out_port: Stream(Bit(8)) out;
+            }
+
+            impl top of top_s {
+                for i in range(3) {
+                    instance c(test_i8);
+                }
+                instance c_narrow(test_i<4>);
+
+                in_port => c[0].in_port;
+                c[0].out_port => c[1].in_port;
+                c[0].out_port => c[2].in_port;
+                c[1].out_port => out_port;
+                c[2].out_port => c_narrow.in_port;
+            }
+            "#);
+            let src_pack1 = String::from(r#"
+            package std;
+            streamlet void_s<type_in: type> {
+                input_port: type_in in;
+            }
+
+            impl void_i<type_in: type> of void_s<type_in> @External @NoTemplateExpansion {
+
+            }
+
+            streamlet duplicator_s<type_in: type, N: int> {
+                input_port: type_in in;
+                for i in range(N) {
+                    output_port: type_in out;
+                }
+            }
+
+            impl duplicator_i<type_in: type, N: int> of duplicator_s<type_in, N> @External @NoTemplateExpansion {
+
+            }
+            "#);
+
+            let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+            let status = project_write.add_package(format!("./pack1.td"), src_pack1);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        project.read().unwrap().evaluate_target(format!("top"), format!("pack")).expect("fail to evaluate");
+        sugaring_auto_insertion_duplicator_voider::sugaring_add_duplicator_voider(project.clone(), format!("top"), format!("pack")).expect("fail to sugar");
+        return generate_json_representation_from_tydi_project(project.clone(), format!("top"), format!("pack")).expect("fail to generate json");
+    };
+
+    let json_output_0 = compile();
+    let json_output_1 = compile();
+    assert_eq!(json_output_0, json_output_1);
+    println!("{}", json_output_0);
+}
+
+#[test]
+fn logic_type_array() {
+    let project = Project::new(format!("sample_project"));
+    {
+        let mut project_write = project.write().unwrap();
+
+        let src_pack0 = String::from(r#"
+        package pack0;
+
+        n = 4;
+        bytes = Bit(8)[n];
+        third_byte = bytes[2];
+        Group word {
+            lanes: Bit(8)[4];
+            last: bytes;
+        }
+        word_streams = Stream(word)[2];
+
+        streamlet top_s {
+            port_in: word_streams[0] in;
+            port_out: word_streams[1] out;
+            port_byte: Stream(third_byte) out;
+        }
+
+        impl top_i of top_s {
+            self.port_in => self.port_out;
+        }
+
+        "#);
+
+        let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+        if status.is_err() {
+            panic!("{}", status.err().unwrap().print());
+        }
+    }
+    project.read().unwrap().evaluate_target(format!("top_i"), format!("pack0")).expect("fail to evaluate");
+
+    let json_output = generate_json_representation_from_tydi_project(project.clone(), format!("top_i"), format!("pack0")).expect("fail to generate json");
+    println!("{}", json_output);
+    assert!(json_output.contains("\"type\": \"Array\""));
+    assert!(json_output.contains("\"size\": 4"));
+    assert!(!json_output.contains("lanes_for0"));
+}
+
+#[test]
+fn port_bundle() {
+    let project = Project::new(format!("sample_project"));
+    {
+        let mut project_write = project.write().unwrap();
+
+        let src_pack0 = String::from(r#"
+        package pack0;
+
+        interface req_resp<W: int> {
+            req: Stream(Bit(W)) out;
+            resp: Stream(Bit(8)) in;
+        }
+
+        streamlet master_s {
+            m: bundle req_resp<16>;
+        }
+
+        impl master_i of master_s {}
+
+        streamlet top_s {
+            up: bundle req_resp<16>;
+        }
+
+        impl top_i of top_s {
+            instance master(master_i);
+            self.up => master.m;
+        }
+
+        "#);
+
+        let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+        if status.is_err() {
+            panic!("{}", status.err().unwrap().print());
+        }
+    }
+    project.read().unwrap().evaluate_target(format!("top_i"), format!("pack0")).expect("fail to evaluate");
+
+    let json_output = generate_json_representation_from_tydi_project(project.clone(), format!("top_i"), format!("pack0")).expect("fail to generate json");
+    println!("{}", json_output);
+    assert!(json_output.contains("\"streamlet_top_s__up_req\""));
+    assert!(json_output.contains("\"streamlet_top_s__up_resp\""));
+    assert!(json_output.contains("\"src_port_name\": \"streamlet_master_s__m_req\""));
+    assert!(json_output.contains("\"sink_port_name\": \"streamlet_master_s__m_resp\""));
+}
+
+#[test]
+fn port_bundle_in_for() {
+    let project = Project::new(format!("sample_project"));
+    {
+        let mut project_write = project.write().unwrap();
+
+        let src_pack0 = String::from(r#"
+        package pack0;
+
+        interface req_resp {
+            req: Stream(Bit(16)) out;
+            resp: Stream(Bit(8)) in;
+        }
+
+        streamlet master_s {
+            m: bundle req_resp;
+        }
+
+        impl master_i of master_s {}
+
+        streamlet slave_s {
+            s: flip bundle req_resp;
+        }
+
+        impl slave_i of slave_s {}
+
+        streamlet top_s {}
+
+        impl top_i of top_s {
+            for i in range(2) {
+                instance master(master_i);
+                instance slave(slave_i);
+                master.m => slave.s;
+            }
+        }
+
+        "#);
+
+        let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+        if status.is_err() {
+            panic!("{}", status.err().unwrap().print());
+        }
+    }
+    project.read().unwrap().evaluate_target(format!("top_i"), format!("pack0")).expect("fail to evaluate");
+
+    let json_output = generate_json_representation_from_tydi_project(project.clone(), format!("top_i"), format!("pack0")).expect("fail to generate json");
+    println!("{}", json_output);
+    assert_eq!(json_output.matches("\"src_port_name\": \"streamlet_master_s__m_req\"").count(), 2);
+    assert_eq!(json_output.matches("\"src_port_name\": \"streamlet_slave_s__s_resp\"").count(), 2);
+}
+
+#[test]
+fn bulk_net() {
+    let project = Project::new(format!("sample_project"));
+    {
+        let mut project_write = project.write().unwrap();
+
+        let src_pack0 = String::from(r#"
+        package pack;
+        use std;
+
+        bit8_stream = Stream(Bit(8));
+
+        streamlet stage_s {
+            data_in: bit8_stream in;
+            last_in: Stream(Bit(1)) in;
+            data_out: bit8_stream out;
+            last_out: Stream(Bit(1)) out;
+        }
+
+        impl stage_i of stage_s @External {}
+
+        impl top of stage_s {
+            instance s0(stage_i);
+            instance s1(stage_i);
+            self.* => s0.*;
+            self.* => s1.*;
+            s0.* => self.*;
+        }
+
+        "#);
+        let src_pack1 = String::from(r#"
+        package std;
+        streamlet void_s<type_in: type> {
+            input_port: type_in in;
+        }
+
+        impl void_i<type_in: type> of void_s<type_in> @External {}
+
+        streamlet duplicator_s<type_in: type, N: int> {
+            input_port: type_in in;
+            for i in range(N) {
+                output_port: type_in out;
+            }
+        }
+
+        impl duplicator_i<type_in: type, N: int> of duplicator_s<type_in, N> @External {}
+        "#);
+
+        let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+        if status.is_err() {
+            panic!("{}", status.err().unwrap().print());
+        }
+        let status = project_write.add_package(format!("./pack1.td"), src_pack1);
+        if status.is_err() {
+            panic!("{}", status.err().unwrap().print());
+        }
+    }
+    project.read().unwrap().evaluate_target(format!("top"), format!("pack")).expect("fail to evaluate");
+    //the nets expanded from the bulk nets are replaced by nets to duplicators
+    sugaring_auto_insertion_duplicator_voider::sugaring_add_duplicator_voider(project.clone(), format!("top"), format!("pack")).expect("fail to sugar");
+
+    let json_output = generate_json_representation_from_tydi_project(project.clone(), format!("top"), format!("pack")).expect("fail to generate json");
+    println!("{}", json_output);
+    assert!(json_output.contains("duplicator_self_data_in"));
+    assert!(json_output.contains("duplicator_self_last_in"));
+    assert!(json_output.contains("voider_s1_data_out"));
+    assert!(json_output.contains("voider_s1_last_out"));
+    assert!(json_output.contains("\"src_port_name\": \"streamlet_stage_s__last_out\""));
+}
+
+#[test]
+fn bulk_net_in_for() {
+    let project = Project::new(format!("sample_project"));
+    {
+        let mut project_write = project.write().unwrap();
+
+        let src_pack0 = String::from(r#"
+        package pack;
+
+        streamlet producer_s {
+            data: Stream(Bit(8)) out;
+            last: Stream(Bit(1)) out;
+        }
+
+        impl producer_i of producer_s @External {}
+
+        streamlet consumer_s {
+            data: Stream(Bit(8)) in;
+            last: Stream(Bit(1)) in;
+        }
+
+        impl consumer_i of consumer_s @External {}
+
+        streamlet top_s {}
+
+        impl top of top_s {
+            for i in range(2) {
+                instance p(producer_i);
+                instance c(consumer_i);
+                p.* => c.*;
+            }
+        }
+
+        "#);
+
+        let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+        if status.is_err() {
+            panic!("{}", status.err().unwrap().print());
+        }
+    }
+    project.read().unwrap().evaluate_target(format!("top"), format!("pack")).expect("fail to evaluate");
+
+    let json_output = generate_json_representation_from_tydi_project(project.clone(), format!("top"), format!("pack")).expect("fail to generate json");
+    println!("{}", json_output);
+    assert_eq!(json_output.matches("\"src_port_name\": \"streamlet_producer_s__data\"").count(), 2);
+    assert_eq!(json_output.matches("\"src_port_name\": \"streamlet_producer_s__last\"").count(), 2);
+}
+
+#[test]
+fn template_instances_with_different_type_arguments() {
+    let project = Project::new(format!("sample_project"));
+    {
+        let mut project_write = project.write().unwrap();
+
+        let src_pack0 = String::from(r#"
+        package p;
+
+        streamlet dbg_s<T: type> {
+            input: Stream(T) in;
+        }
+
+        impl dbg<T: type> of dbg_s<T> @External {}
+
+        streamlet dup_s<T: type> {
+            input: Stream(T) in;
+        }
+
+        impl dup_i<T: type> of dup_s<T> {
+            instance d(dbg<T>);
+            self.input => d.input;
+        }
+
+        streamlet top_s {
+            a: Stream(Bit(8)) in;
+            b: Stream(Bit(16)) in;
+        }
+
+        impl top of top_s {
+            instance x(dup_i<Bit(8)>);
+            instance y(dup_i<Bit(16)>);
+            self.a => x.input;
+            self.b => y.input;
+        }
+
+        "#);
+
+        let status = project_write.add_package(format!("./p.td"), src_pack0);
+        if status.is_err() {
+            panic!("{}", status.err().unwrap().print());
+        }
+    }
+    project.read().unwrap().evaluate_target(format!("top"), format!("p")).expect("fail to evaluate");
+
+    let json_output = generate_json_representation_from_tydi_project(project.clone(), format!("top"), format!("p")).expect("fail to generate json");
+    println!("{}", json_output);
+    //dbg<T> is derived with the same expression in both instances of dup_i, but T is Bit(8) in one and Bit(16) in the other
+    let dbg_streamlets: std::collections::BTreeSet<&str> = json_output.lines().filter(|line| line.contains("\"derived_streamlet\": \"package_p__instance_dbg_s_")).collect();
+    assert_eq!(dbg_streamlets.len(), 2);
+    //Stream(T) is parsed again in each instance, the generated names of its properties must not collide
+    let json_value: serde_json::Value = serde_json::from_str(&json_output).expect("fail to parse json");
+    let stream_type_widths: std::collections::BTreeSet<String> = json_value["logic_types"].as_object().unwrap().iter()
+        .filter(|(name, _)| name.starts_with("predefined__stream_type_"))
+        .map(|(_, logic_type)| logic_type["value"].to_string()).collect();
+    assert_eq!(stream_type_widths, std::collections::BTreeSet::from([format!("8"), format!("16")]));
+}
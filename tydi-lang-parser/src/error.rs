@@ -38,6 +38,25 @@ impl TydiLangError {
         };
     }
 
+    /// errors raised deep in an expression might not know where they are, use the given location for them
+    pub fn with_default_location(mut self, location: Option<CodeLocation>) -> Self {
+        let location = match location {
+            Some(location) => location,
+            None => return self,
+        };
+        if self.location.iter().all(|single_location| single_location.begin.is_none()) {
+            self.location = vec![location];
+        }
+        else {
+            for single_location in self.location.iter_mut() {
+                if single_location.begin.is_none() {
+                    *single_location = location.clone();
+                }
+            }
+        }
+        return self;
+    }
+
     pub fn print(&self) -> String {
         let mut output_string = String::new();
         output_string.push_str(&format!("{}\n", self.message));
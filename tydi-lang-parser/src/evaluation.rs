@@ -28,6 +28,10 @@ pub mod evaluate_streamlet;
 #[allow(unused_imports)]
 pub(in crate) use evaluate_streamlet::*;
 
+pub mod logic_type_compatibility;
+#[allow(unused_imports)]
+pub(in crate) use logic_type_compatibility::*;
+
 pub mod evaluate_impl;
 #[allow(unused_imports)]
 pub(in crate) use evaluate_impl::*;
@@ -1,9 +1,14 @@
 use std::sync::{Arc, RwLock};
 
+use crate::deep_clone::DeepClone_ArcLock;
 use crate::error::TydiLangError;
 use crate::evaluation::predefined_function::get_function;
-use crate::tydi_memory_representation::{Function, Scope, TypedValue};
-use crate::evaluation::Evaluator;
+use crate::trait_common::GetName;
+use crate::tydi_memory_representation::{Function, FunctionDefinition, Scope, TypedValue, Variable, CodeLocation, ScopeRelationType, TraitCodeLocationAccess, GetScope};
+use crate::evaluation::{Evaluator, evaluate_expression, evaluate_id_in_typed_value, evaluate_var, convert_template_arg_value, is_template_arg_compatible, template_arg_type_to_string};
+
+// the maximum number of nested user-defined function calls, this stops infinite recursion
+pub const MAX_FUNCTION_CALL_DEEPTH: usize = 64;
 
 pub trait FunctionTrait {
     fn get_id() -> String where Self: Sized;
@@ -11,12 +16,88 @@ pub trait FunctionTrait {
     fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError>;
 }
 
-pub fn evaluate_function(function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+// function_scope is where the function is looked up, e.g. the package scope of "pack.func()", while the arguments are always evaluated in scope
+pub fn evaluate_function(function: Arc<RwLock<Function>>, function_scope: Arc<RwLock<Scope>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+    //a user-defined function is called, any other variable with the same name in function_scope shadows the predefined function
+    let function_id = function.read().unwrap().get_function_id();
+    let result = Scope::resolve_identifier(&function_id, &None, &CodeLocation::new_unknown(), function_scope.clone(), function_scope.clone(), ScopeRelationType::resolve_id_default(), evaluator.clone());
+    if let Ok((function_var, _)) = result {
+        let function_var_value = function_var.read().unwrap().get_value();
+        if let TypedValue::FunctionDefinition(function_definition) = function_var_value {
+            return evaluate_user_function(function_definition.clone(), function.clone(), scope.clone(), evaluator.clone());
+        }
+        return Err(TydiLangError::new_multiple_locations(format!("{} is not a function", &function_id), vec![function.read().unwrap().get_code_location(), function_var.read().unwrap().get_code_location()]));
+    }
+
     let function_instance = get_function(function.clone())?;
     let value = function_instance.execute(function.clone(), scope.clone(), evaluator.clone())?;
 
     return Ok(value);
 }
 
+pub fn evaluate_user_function(function_definition: Arc<RwLock<FunctionDefinition>>, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+    let function_name = function_definition.read().unwrap().get_name();
+    let function_location = function.read().unwrap().get_code_location();
+    let function_args = function_definition.read().unwrap().get_args();
+    let function_arg_exps = function.read().unwrap().get_function_arg_exps();
+    if function_args.len() != function_arg_exps.len() {
+        return Err(TydiLangError::new_multiple_locations(format!("function {} has {} argument(s), get {}", &function_name, function_args.len(), function_arg_exps.len()), vec![function_location.clone(), function_definition.read().unwrap().get_code_location()]));
+    }
+
+    let function_call_deepth = evaluator.read().unwrap().get_function_call_deepth();
+    if function_call_deepth >= MAX_FUNCTION_CALL_DEEPTH {
+        return Err(TydiLangError::new(format!("function {} exceeds the maximum call deepth {}, consider infinite recursion", &function_name, MAX_FUNCTION_CALL_DEEPTH), function_location.clone()));
+    }
+
+    //each call works on a fresh copy of the function scope, its parent is still the scope where the function is defined
+    let call_scope = function_definition.read().unwrap().get_scope().read().unwrap().deep_clone_arclock();
+    for index in 0 .. function_args.len() {
+        let function_arg = function_args.get(&index).expect("bug: function arg index not from 0 to n");
+        let function_arg_exp = function_arg_exps.get(&index).expect("bug: function arg exp index not from 0 to n");
+        let arg_value = evaluate_expression(function_arg_exp.clone(), Some(function_location.clone()), scope.clone(), evaluator.clone())?;
+        let arg_value = evaluate_id_in_typed_value(arg_value, Some(function_location.clone()), ScopeRelationType::resolve_id_default(), None, scope.clone(), evaluator.clone())?;
+        let arg_value = match arg_value.try_get_referenced_variable() {
+            Some(real_var) => evaluate_var(real_var.clone(), scope.clone(), evaluator.clone())?,
+            None => arg_value,
+        };
+        let arg_type = function_arg.get_type_indication();
+        let arg_value = convert_template_arg_value(&arg_type, arg_value);
+        if !is_template_arg_compatible(function_arg.get_is_array(), &arg_type, &arg_value) {
+            return Err(TydiLangError::new(format!("function {}, argument {} expects {}, get {}", &function_name, function_arg.get_name(), template_arg_type_to_string(function_arg.get_is_array(), &arg_type), arg_value.get_brief_info()), function_location.clone()));
+        }
+        let arg_var = Variable::new_predefined(function_arg.get_name(), arg_value);
+        arg_var.write().unwrap().set_code_location(function_arg.get_code_location());
+        call_scope.write().unwrap().add_var(arg_var)?;
+    }
 
+    evaluator.write().unwrap().increase_function_call_deepth();
+    let return_value = evaluate_user_function_body(function_definition.clone(), call_scope.clone(), evaluator.clone());
+    evaluator.write().unwrap().decrease_function_call_deepth();
+    let return_value = return_value?;
 
+    let return_type = function_definition.read().unwrap().get_return_type();
+    match return_type {
+        Some(return_type) => {
+            let return_type_indication = return_type.get_type_indication();
+            let return_value = convert_template_arg_value(&return_type_indication, return_value);
+            if !is_template_arg_compatible(return_type.get_is_array(), &return_type_indication, &return_value) {
+                return Err(TydiLangError::new_multiple_locations(format!("function {} should return {}, get {}", &function_name, template_arg_type_to_string(return_type.get_is_array(), &return_type_indication), return_value.get_brief_info()), vec![function_location.clone(), return_type.get_code_location()]));
+            }
+            return Ok(return_value);
+        },
+        None => return Ok(return_value),
+    }
+}
+
+fn evaluate_user_function_body(function_definition: Arc<RwLock<FunctionDefinition>>, call_scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+    //statements in the function body, e.g. assert(...)
+    crate::post_compile::check_assert::check_scope(call_scope.clone(), evaluator.clone())?;
+
+    let return_var = function_definition.read().unwrap().get_return_exp().read().unwrap().deep_clone_arclock();
+    let return_value = evaluate_var(return_var.clone(), call_scope.clone(), evaluator.clone())?;
+    let return_value = match return_value.try_get_referenced_variable() {
+        Some(real_var) => evaluate_var(real_var.clone(), call_scope.clone(), evaluator.clone())?,
+        None => return_value,
+    };
+    return Ok(return_value);
+}
@@ -1,14 +1,15 @@
 use std::sync::{Arc, RwLock};
+use std::collections::BTreeMap;
 
 use evaluate_var::evaluate_id_in_typed_value;
 
 use crate::generate_name::generate_init_value;
 use crate::trait_common::{GetName, HasDocument};
-use crate::tydi_memory_representation::{InstanceType, Scope, TypedValue, GetScope, Implementation, TraitCodeLocationAccess, Variable, Instance, Net, CodeLocation, ScopeType, PortOwner, ScopeRelationType};
+use crate::tydi_memory_representation::{InstanceType, Scope, TypedValue, GetScope, Implementation, TraitCodeLocationAccess, Variable, Instance, Net, CodeLocation, ScopeType, PortOwner, ScopeRelationType, Attribute, Port, PortBundle, PortDirection, TypeIndication};
 
 use crate::error::TydiLangError;
 
-use super::{Evaluator, evaluate_var, evaluate_scope, ScopeOwner, evaluate_expression};
+use super::{Evaluator, evaluate_var, evaluate_scope, ScopeOwner, evaluate_expression, check_logic_type_strict, check_logic_type_structural};
 
 
 pub fn evaluate_impl(target: Arc<RwLock<Implementation>>, _scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
@@ -21,6 +22,9 @@ pub fn evaluate_impl(target: Arc<RwLock<Implementation>>, _scope: Arc<RwLock<Sco
         TypedValue::Streamlet(s) => s,
         _ => return Err(TydiLangError::new(format!("{} is not a streamlet, but used in defining impl({})", target_derived_streamlet.read().unwrap().get_name(), target.read().unwrap().get_name()), target_derived_streamlet.read().unwrap().get_code_location()))
     };
+    if derived_streamlet.read().unwrap().get_is_interface() {
+        return Err(TydiLangError::new(format!("{} is an interface, it can only be used as a port bundle, but used in defining impl({})", derived_streamlet.read().unwrap().get_name(), target.read().unwrap().get_name()), target_derived_streamlet.read().unwrap().get_code_location()));
+    }
     {
         let mut target_write = target.write().unwrap();
         target_write.set_derived_streamlet(Some(derived_streamlet.clone()));
@@ -84,11 +88,16 @@ pub fn evaluate_instance(target: Arc<RwLock<Instance>>, scope: Arc<RwLock<Scope>
 
 
 pub fn evaluate_net(target: Arc<RwLock<Net>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+    if target.read().unwrap().get_is_bulk() {
+        return evaluate_bulk_net(target.clone(), scope.clone(), evaluator.clone());
+    }
+
     //evaluate lhs
     let lhs_port_var = target.read().unwrap().get_source();
     let lhs_port_value = evaluate_var(lhs_port_var.clone(), scope.clone(), evaluator.clone())?;
     let lhs_port = match &lhs_port_value {
         TypedValue::Port(port) => port.clone(),
+        TypedValue::PortBundle(port_bundle) => return evaluate_port_bundle_net(target.clone(), port_bundle.clone(), scope.clone(), evaluator.clone()),
         _ => return Err(TydiLangError::new(format!("{} is not a port, but used in defining net {}", lhs_port_var.read().unwrap().get_name(), target.read().unwrap().get_name()), target.read().unwrap().get_code_location())),
     };
     target.write().unwrap().set_source_port(Some(lhs_port.clone()));
@@ -97,65 +106,335 @@ pub fn evaluate_net(target: Arc<RwLock<Net>>, scope: Arc<RwLock<Scope>>, evaluat
     let rhs_port_value = evaluate_var(rhs_port_var.clone(), scope.clone(), evaluator.clone())?;
     let rhs_port = match &rhs_port_value {
         TypedValue::Port(port) => port.clone(),
+        TypedValue::PortBundle(port_bundle) => return Err(TydiLangError::new(format!("net ({}) connects port {} to port bundle {}, but a port bundle can only be connected to another port bundle", get_net_brief_info(target.clone()), get_port_brief_name(lhs_port.clone()), port_bundle.read().unwrap().get_name()), target.read().unwrap().get_code_location())),
         _ => return Err(TydiLangError::new(format!("{} is not a port, but used in defining net {}", lhs_port_var.read().unwrap().get_name(), target.read().unwrap().get_name()), target.read().unwrap().get_code_location())),
     };
     target.write().unwrap().set_sink_port(Some(rhs_port.clone()));
 
-    let get_port_owner_from_exp = |port_var: Arc<RwLock<Variable>>| -> Result<PortOwner, TydiLangError> {
-        use crate::pest::Parser;
-        use crate::tydi_parser::{Rule, TydiLangSrc};
-        let port_owner_exp = port_var.read().unwrap().get_exp();
-        let port_owner_exp = match port_owner_exp {
-            Some(exp) => exp,
-            None => return Err(TydiLangError::new(format!("{} of net {} has no port owner expression", port_var.read().unwrap().get_name(), target.read().unwrap().get_name()), target.read().unwrap().get_code_location())),
-        };
-        let port_owner_pest = TydiLangSrc::parse(Rule::Exp,&port_owner_exp).unwrap();
-        let mut port_owner_name = generate_init_value();
-        let mut counter = 0;
-        for ele_exp in port_owner_pest.into_iter() {
-            for element in ele_exp.into_inner().into_iter(){
-                match element.as_rule() {
-                    Rule::Term => {     //we only care about the first term because it's the port owner
-                        if counter == 0 {
-                            port_owner_name = element.as_str().to_string();
-                        }
-                        counter += 1;
-                    },
-                    _ => (),    //ignore
-                }
+    let lhs_port_owner = get_port_owner_from_exp(target.clone(), lhs_port_var.clone(), scope.clone(), evaluator.clone())?;
+    target.write().unwrap().set_source_port_owner(lhs_port_owner.clone());
+    let rhs_port_owner = get_port_owner_from_exp(target.clone(), rhs_port_var.clone(), scope.clone(), evaluator.clone())?;
+    target.write().unwrap().set_sink_port_owner(rhs_port_owner.clone());
+
+    check_net(target.clone())?;
+
+    return Ok(TypedValue::Net(target.clone()));
+}
+
+//a net between two port bundles is expanded into one net for each port of the bundles
+fn evaluate_port_bundle_net(target: Arc<RwLock<Net>>, lhs_port_bundle: Arc<RwLock<PortBundle>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+    let lhs_port_bundle_var = target.read().unwrap().get_source();
+    let rhs_port_bundle_var = target.read().unwrap().get_sink();
+    let rhs_port_bundle_value = evaluate_var(rhs_port_bundle_var.clone(), scope.clone(), evaluator.clone())?;
+    let rhs_port_bundle = match &rhs_port_bundle_value {
+        TypedValue::PortBundle(port_bundle) => port_bundle.clone(),
+        _ => return Err(TydiLangError::new(format!("net ({}) connects port bundle {} to {}, but a port bundle can only be connected to another port bundle", get_net_brief_info(target.clone()), lhs_port_bundle.read().unwrap().get_name(), rhs_port_bundle_value.get_brief_info()), target.read().unwrap().get_code_location())),
+    };
+
+    let lhs_port_owner = get_port_owner_from_exp(target.clone(), lhs_port_bundle_var.clone(), scope.clone(), evaluator.clone())?;
+    let rhs_port_owner = get_port_owner_from_exp(target.clone(), rhs_port_bundle_var.clone(), scope.clone(), evaluator.clone())?;
+
+    let lhs_ports = lhs_port_bundle.read().unwrap().get_ports();
+    let rhs_ports = rhs_port_bundle.read().unwrap().get_ports();
+    for (port_bundle, ports, other_port_bundle, other_ports) in [(&lhs_port_bundle, &lhs_ports, &rhs_port_bundle, &rhs_ports), (&rhs_port_bundle, &rhs_ports, &lhs_port_bundle, &lhs_ports)] {
+        for port_name in ports.keys() {
+            if !other_ports.contains_key(port_name) {
+                return Err(TydiLangError::new_multiple_locations(
+                    format!("net ({}) connects port bundles with different ports, port {} of bundle {} has no counterpart in bundle {}", get_net_brief_info(target.clone()), port_name, port_bundle.read().unwrap().get_name(), other_port_bundle.read().unwrap().get_name()),
+                    vec![target.read().unwrap().get_code_location(), port_bundle.read().unwrap().get_code_location(), other_port_bundle.read().unwrap().get_code_location()]
+                ));
             }
         }
-        assert!(port_owner_name != generate_init_value());
-        let port_owner = if port_owner_name == String::from("self") || counter == 1 {
-            PortOwner::ImplSelf
+    }
+
+    let mut output_nets = vec![];
+    for (port_name, lhs_port) in lhs_ports {
+        let rhs_port = rhs_ports[&port_name].clone();
+        //the port that drives the net becomes the source, so the net follows the directions declared in the interface
+        let lhs_port_direction = lhs_port.read().unwrap().get_direction();
+        let (output_net, source_var, sink_var) = if is_port_driver(&lhs_port_owner, &lhs_port_direction) {
+            (Net::new_with_known_src_sink(lhs_port.clone(), lhs_port_owner.clone(), rhs_port.clone(), rhs_port_owner.clone()), lhs_port_bundle_var.clone(), rhs_port_bundle_var.clone())
         }
         else {
-            let port_owner_value = evaluate_expression(port_owner_name.clone(), Some(target.read().unwrap().get_code_location()), scope.clone(), evaluator.clone())?;
-            let port_owner_value = evaluate_id_in_typed_value(port_owner_value, Some(target.read().unwrap().get_code_location()), ScopeRelationType::resolve_id_in_current_scope(), None, scope.clone(), evaluator.clone())?;
-            
-            let port_owner_inst = match port_owner_value {
-                TypedValue::Instance(inst) => inst,
-                TypedValue::RefToVar(var) => {
-                    evaluate_var(var.clone(), scope.clone(), evaluator.clone())?;
-                    let var_value = var.read().unwrap().get_value();
-                    match var_value {
-                        TypedValue::Instance(inst) => {
-                            inst
-                        },
-                        _ => unreachable!(),
+            (Net::new_with_known_src_sink(rhs_port.clone(), rhs_port_owner.clone(), lhs_port.clone(), lhs_port_owner.clone()), rhs_port_bundle_var.clone(), lhs_port_bundle_var.clone())
+        };
+        {
+            let target_read = target.read().unwrap();
+            let mut output_net_write = output_net.write().unwrap();
+            output_net_write.set_source(source_var);
+            output_net_write.set_sink(sink_var);
+            output_net_write.set_name(format!("{}_{}", target_read.get_name(), port_name));
+            output_net_write.set_document(target_read.get_document());
+            output_net_write.set_attributes(target_read.get_attributes());
+            output_net_write.set_code_location(target_read.get_code_location());
+            output_net_write.set_parent_impl(target_read.get_parent_impl());
+        }
+        check_net(output_net.clone())?;
+        output_nets.push(TypedValue::Net(output_net));
+    }
+
+    return Ok(TypedValue::Array(output_nets));
+}
+
+//a bulk net is expanded into one net for each port of the source owner that drives a same-named port of the sink owner
+fn evaluate_bulk_net(target: Arc<RwLock<Net>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+    let source_owner_var = target.read().unwrap().get_source();
+    let sink_owner_var = target.read().unwrap().get_sink();
+    let source_owner_exp = source_owner_var.read().unwrap().get_exp().unwrap_or(format!("???"));
+    let sink_owner_exp = sink_owner_var.read().unwrap().get_exp().unwrap_or(format!("???"));
+    let bulk_net_brief_info = format!("{}.* => {}.*", source_owner_exp, sink_owner_exp);
+
+    let (source_owner, source_ports) = get_bulk_net_ports(&bulk_net_brief_info, source_owner_var.clone(), true, scope.clone(), evaluator.clone())?;
+    let (sink_owner, sink_ports) = get_bulk_net_ports(&bulk_net_brief_info, sink_owner_var.clone(), false, scope.clone(), evaluator.clone())?;
+
+    //report all unmatched ports at once
+    let mut unmatched_ports = vec![];
+    let mut unmatched_port_locations = vec![target.read().unwrap().get_code_location()];
+    for (owner_exp, ports, other_ports, direction_info) in [(&source_owner_exp, &source_ports, &sink_ports, "receive from it"), (&sink_owner_exp, &sink_ports, &source_ports, "drive it")] {
+        for (port_name, ports_value) in ports {
+            if other_ports.contains_key(port_name) { continue; }
+            unmatched_ports.push(format!("{}.{} (no same-named port to {})", owner_exp, port_name, direction_info));
+            if let TypedValue::Port(port) = ports_value {
+                unmatched_port_locations.push(port.read().unwrap().get_code_location());
+            }
+        }
+    }
+    if !unmatched_ports.is_empty() {
+        return Err(TydiLangError::new_multiple_locations(format!("bulk net ({}) has unmatched ports: {}", bulk_net_brief_info, unmatched_ports.join(", ")), unmatched_port_locations));
+    }
+
+    //pair the ports by name, the elements of port arrays are paired by index
+    let mut port_pairs = vec![];
+    for (port_name, source_value) in &source_ports {
+        let sink_value = &sink_ports[port_name];
+        match (source_value, sink_value) {
+            (TypedValue::Port(source_port), TypedValue::Port(sink_port)) => {
+                port_pairs.push((port_name.clone(), port_name.clone(), source_port.clone(), sink_port.clone()));
+            },
+            (TypedValue::Array(source_array), TypedValue::Array(sink_array)) if source_array.len() == sink_array.len() => {
+                for (index, (source_element, sink_element)) in source_array.iter().zip(sink_array.iter()).enumerate() {
+                    if let (TypedValue::Port(source_port), TypedValue::Port(sink_port)) = (source_element, sink_element) {
+                        port_pairs.push((format!("{}_{}", port_name, index), format!("{}[{}]", port_name, index), source_port.clone(), sink_port.clone()));
+                    }
+                }
+            },
+            _ => return Err(TydiLangError::new(format!("bulk net ({}) cannot connect {}.{} to {}.{}, they must be both ports or both port arrays of the same size", bulk_net_brief_info, source_owner_exp, port_name, sink_owner_exp, port_name), target.read().unwrap().get_code_location())),
+        }
+    }
+    if port_pairs.is_empty() {
+        return Err(TydiLangError::new(format!("bulk net ({}) connects no ports, {} has no port to drive it or {} has no port to receive from it", bulk_net_brief_info, source_owner_exp, sink_owner_exp), target.read().unwrap().get_code_location()));
+    }
+
+    let mut output_nets = vec![];
+    for (net_suffix, port_exp, source_port, sink_port) in port_pairs {
+        let output_net = Net::new_with_known_src_sink(source_port.clone(), source_owner.clone(), sink_port.clone(), sink_owner.clone());
+        {
+            let target_read = target.read().unwrap();
+            let net_name = format!("{}_{}", target_read.get_name(), net_suffix);
+            //the expressions are only used to describe the expanded net in error messages
+            let source_var = Variable::new_with_type_indication(format!("{}_source", net_name), Some(format!("{}.{}", source_owner_exp, port_exp)), TypeIndication::AnyPort);
+            source_var.write().unwrap().set_code_location(source_owner_var.read().unwrap().get_code_location());
+            let sink_var = Variable::new_with_type_indication(format!("{}_sink", net_name), Some(format!("{}.{}", sink_owner_exp, port_exp)), TypeIndication::AnyPort);
+            sink_var.write().unwrap().set_code_location(sink_owner_var.read().unwrap().get_code_location());
+
+            let mut output_net_write = output_net.write().unwrap();
+            output_net_write.set_source(source_var);
+            output_net_write.set_sink(sink_var);
+            output_net_write.set_name(net_name);
+            output_net_write.set_document(target_read.get_document());
+            output_net_write.set_attributes(target_read.get_attributes());
+            output_net_write.set_code_location(target_read.get_code_location());
+            output_net_write.set_parent_impl(target_read.get_parent_impl());
+        }
+        check_net(output_net.clone())?;
+        output_nets.push(TypedValue::Net(output_net));
+    }
+
+    return Ok(TypedValue::Array(output_nets));
+}
+
+//get the port owner of one side of a bulk net and its ports that can drive (source) or receive from (sink) a net, indexed by port name
+fn get_bulk_net_ports(bulk_net_brief_info: &String, owner_var: Arc<RwLock<Variable>>, is_source: bool, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<(PortOwner, BTreeMap<String, TypedValue>), TydiLangError> {
+    let owner_value = evaluate_var(owner_var.clone(), scope.clone(), evaluator.clone())?;
+    let owner_inst = match &owner_value {
+        TypedValue::Instance(inst) => inst.clone(),
+        _ => return Err(TydiLangError::new(format!("{} is not an instance, but used in bulk net ({})", owner_var.read().unwrap().get_exp().unwrap_or(owner_var.read().unwrap().get_name()), bulk_net_brief_info), owner_var.read().unwrap().get_code_location())),
+    };
+    let port_owner = if owner_inst.read().unwrap().get_inst_type() == InstanceType::SelfInst {
+        PortOwner::ImplSelf
+    }
+    else {
+        PortOwner::ImplInstance(owner_inst.clone())
+    };
+
+    let derived_impl = owner_inst.read().unwrap().get_derived_impl().expect("bug: the instance of a bulk net is not evaluated");
+    let derived_streamlet = derived_impl.read().unwrap().get_derived_streamlet().expect("bug: the implementation of a bulk net is not evaluated");
+    let streamlet_vars = derived_streamlet.read().unwrap().get_scope().read().unwrap().get_variables();
+
+    let mut output_ports = BTreeMap::new();
+    for (var_name, var) in streamlet_vars {
+        let var_value = var.read().unwrap().get_value();
+        let first_port = match &var_value {
+            TypedValue::Port(port) => port.clone(),
+            TypedValue::Array(array) => match array.first() {
+                Some(TypedValue::Port(port)) => port.clone(),
+                _ => continue,
+            },
+            _ => continue,
+        };
+        let direction = first_port.read().unwrap().get_direction();
+        let usable = if is_source { is_port_driver(&port_owner, &direction) } else { is_port_receiver(&port_owner, &direction) };
+        if usable {
+            output_ports.insert(var_name, var_value);
+        }
+    }
+
+    return Ok((port_owner, output_ports));
+}
+
+fn get_port_owner_from_exp(target: Arc<RwLock<Net>>, port_var: Arc<RwLock<Variable>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<PortOwner, TydiLangError> {
+    use crate::pest::Parser;
+    use crate::tydi_parser::{Rule, TydiLangSrc};
+    let port_owner_exp = port_var.read().unwrap().get_exp();
+    let port_owner_exp = match port_owner_exp {
+        Some(exp) => exp,
+        None => return Err(TydiLangError::new(format!("{} of net {} has no port owner expression", port_var.read().unwrap().get_name(), target.read().unwrap().get_name()), target.read().unwrap().get_code_location())),
+    };
+    let port_owner_pest = TydiLangSrc::parse(Rule::Exp,&port_owner_exp).unwrap();
+    let mut port_owner_name = generate_init_value();
+    let mut counter = 0;
+    for ele_exp in port_owner_pest.into_iter() {
+        for element in ele_exp.into_inner().into_iter(){
+            match element.as_rule() {
+                Rule::Term => {     //we only care about the first term because it's the port owner
+                    if counter == 0 {
+                        port_owner_name = element.as_str().to_string();
                     }
+                    counter += 1;
+                },
+                _ => (),    //ignore
+            }
+        }
+    }
+    assert!(port_owner_name != generate_init_value());
+    let port_owner = if port_owner_name == String::from("self") || counter == 1 {
+        PortOwner::ImplSelf
+    }
+    else {
+        let port_owner_value = evaluate_expression(port_owner_name.clone(), Some(target.read().unwrap().get_code_location()), scope.clone(), evaluator.clone())?;
+        let port_owner_value = evaluate_id_in_typed_value(port_owner_value, Some(target.read().unwrap().get_code_location()), ScopeRelationType::resolve_id_in_current_scope(), None, scope.clone(), evaluator.clone())?;
+        
+        let port_owner_inst = match port_owner_value {
+            TypedValue::Instance(inst) => inst,
+            TypedValue::RefToVar(var) => {
+                evaluate_var(var.clone(), scope.clone(), evaluator.clone())?;
+                let var_value = var.read().unwrap().get_value();
+                match var_value {
+                    TypedValue::Instance(inst) => {
+                        inst
+                    },
+                    _ => unreachable!(),
                 }
-                _ => unreachable!()
-            };
-            PortOwner::ImplInstance(port_owner_inst)
+            }
+            _ => unreachable!()
         };
-        return Ok(port_owner);
+        PortOwner::ImplInstance(port_owner_inst)
     };
+    return Ok(port_owner);
+}
 
-    let lhs_port_owner = get_port_owner_from_exp(lhs_port_var.clone())?;
-    target.write().unwrap().set_source_port_owner(lhs_port_owner);
-    let rhs_port_owner = get_port_owner_from_exp(rhs_port_var.clone())?;
-    target.write().unwrap().set_sink_port_owner(rhs_port_owner);
+//check the directions and the logic types of a net whose ports and port owners are resolved
+fn check_net(target: Arc<RwLock<Net>>) -> Result<(), TydiLangError> {
+    let lhs_port = target.read().unwrap().get_source_port().expect("bug: the source port of the net is not resolved");
+    let lhs_port_owner = target.read().unwrap().get_source_port_owner();
+    let rhs_port = target.read().unwrap().get_sink_port().expect("bug: the sink port of the net is not resolved");
+    let rhs_port_owner = target.read().unwrap().get_sink_port_owner();
 
-    return Ok(TypedValue::Net(target.clone()));
+    //check port directions: the source must drive the net and the sink must receive from it
+    if !is_port_driver(&lhs_port_owner, &lhs_port.read().unwrap().get_direction()) {
+        return Err(TydiLangError::new_multiple_locations(
+            format!("net ({}) uses {} as source, but it cannot drive a net ({})", get_net_brief_info(target.clone()), get_port_brief_name(lhs_port.clone()), get_port_direction_info(&lhs_port_owner, &lhs_port.read().unwrap().get_direction())),
+            vec![target.read().unwrap().get_code_location(), lhs_port.read().unwrap().get_code_location()]
+        ));
+    }
+    if !is_port_receiver(&rhs_port_owner, &rhs_port.read().unwrap().get_direction()) {
+        return Err(TydiLangError::new_multiple_locations(
+            format!("net ({}) uses {} as sink, but it cannot receive from a net ({})", get_net_brief_info(target.clone()), get_port_brief_name(rhs_port.clone()), get_port_direction_info(&rhs_port_owner, &rhs_port.read().unwrap().get_direction())),
+            vec![target.read().unwrap().get_code_location(), rhs_port.read().unwrap().get_code_location()]
+        ));
+    }
+
+    //check logic type compatibility
+    let net_attributes = target.read().unwrap().get_attributes();
+    if !net_attributes.contains(&Attribute::NoStrictTypeChecking) {
+        let lhs_logic_type = lhs_port.read().unwrap().get_logical_type();
+        let rhs_logic_type = rhs_port.read().unwrap().get_logical_type();
+        let (mismatch, mode) = if net_attributes.contains(&Attribute::StrictTypeChecking) {
+            (check_logic_type_strict(lhs_logic_type.clone(), rhs_logic_type.clone()), "strict")
+        }
+        else {
+            (check_logic_type_structural(lhs_logic_type.clone(), rhs_logic_type.clone()), "structural")
+        };
+        if let Some(reason) = mismatch {
+            return Err(TydiLangError::new_multiple_locations(
+                format!("net ({}) connects incompatible logic types ({} check): source port {} and sink port {}, {}. Use @NoTypeCheck to skip this check", get_net_brief_info(target.clone()), mode, get_port_brief_name(lhs_port.clone()), get_port_brief_name(rhs_port.clone()), reason),
+                vec![target.read().unwrap().get_code_location(), lhs_port.read().unwrap().get_code_location(), rhs_port.read().unwrap().get_code_location()]
+            ));
+        }
+    }
+
+    return Ok(());
+}
+
+pub fn get_net_brief_info(net: Arc<RwLock<Net>>) -> String {
+    let source_exp = match net.read().unwrap().get_source().read().unwrap().get_exp() {
+        Some(exp) => exp,
+        None => match net.read().unwrap().get_source_port() {
+            Some(port) => get_port_brief_name(port),   //nets generated by the compiler have no source expression
+            None => format!("???"),
+        },
+    };
+    let sink_exp = match net.read().unwrap().get_sink().read().unwrap().get_exp() {
+        Some(exp) => exp,
+        None => match net.read().unwrap().get_sink_port() {
+            Some(port) => get_port_brief_name(port),
+            None => format!("???"),
+        },
+    };
+    return format!("{} => {}", source_exp, sink_exp);
+}
+
+pub fn get_port_brief_name(port: Arc<RwLock<Port>>) -> String {
+    let id_in_scope = port.read().unwrap().get_id_in_scope();
+    return match id_in_scope {
+        Some(id) => id,
+        None => port.read().unwrap().get_name(),
+    };
+}
+
+// inside an implementation, an input port of self and an output port of an instance drive nets
+fn is_port_driver(owner: &PortOwner, direction: &PortDirection) -> bool {
+    return match (owner, direction) {
+        (PortOwner::ImplSelf, PortDirection::In) => true,
+        (PortOwner::ImplInstance(_), PortDirection::Out) => true,
+        _ => false,
+    };
+}
+
+// inside an implementation, an output port of self and an input port of an instance receive from nets
+fn is_port_receiver(owner: &PortOwner, direction: &PortDirection) -> bool {
+    return match (owner, direction) {
+        (PortOwner::ImplSelf, PortDirection::Out) => true,
+        (PortOwner::ImplInstance(_), PortDirection::In) => true,
+        _ => false,
+    };
+}
+
+fn get_port_direction_info(owner: &PortOwner, direction: &PortDirection) -> String {
+    let owner_info = match owner {
+        PortOwner::Unknown => format!("unknown owner"),
+        PortOwner::ImplSelf => format!("self"),
+        PortOwner::ImplInstance(inst) => format!("instance {}", inst.read().unwrap().get_name()),
+    };
+    return format!("{} port of {}", direction.to_string(), owner_info);
 }
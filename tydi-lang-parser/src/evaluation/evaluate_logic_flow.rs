@@ -14,8 +14,10 @@ pub fn evaluate_for(for_target: Arc<RwLock<For>>, parent_scope: Arc<RwLock<Scope
     let for_array_var_value = evaluate_var(for_array_var.clone(), scope.clone(), evaluator.clone())?;
     let for_array_var_value = match for_array_var_value {
         TypedValue::Array(v) => v,
+        //iterating a map visits its keys in order
+        TypedValue::Map(v) => v.into_keys().map(|key| TypedValue::StringValue(key)).collect(),
         _ => {
-            return Err(TydiLangError::new(format!("\"for\" range variable is not an array, value:{}", for_array_var_value.get_brief_info()), for_array_var.read().unwrap().get_code_location()));
+            return Err(TydiLangError::new(format!("\"for\" range variable is not an array or a map, value:{}", for_array_var_value.get_brief_info()), for_array_var.read().unwrap().get_code_location()));
         }
     };
     let for_scope = for_target.read().unwrap().get_scope();
@@ -106,6 +108,11 @@ pub fn evaluate_for(for_target: Arc<RwLock<For>>, parent_scope: Arc<RwLock<Scope
                     port.write().unwrap().set_name(format!("{}_for{}", current_name, for_evaluation_count));
                     port.write().unwrap().set_id_in_scope(Some(format!("{}_for{}", current_name, for_evaluation_count)));
                 },
+                TypedValue::PortBundle(bundle) => {
+                    let current_name = var_name.clone();
+                    bundle.write().unwrap().set_name(format!("{}_for{}", current_name, for_evaluation_count));
+                    bundle.write().unwrap().set_id_in_scope(Some(format!("{}_for{}", current_name, for_evaluation_count)));
+                },
                 TypedValue::Implementation(_) => unreachable!(),
                 TypedValue::Instance(inst) => {
                     // let current_name = inst.read().unwrap().get_name();
@@ -126,12 +133,26 @@ pub fn evaluate_for(for_target: Arc<RwLock<For>>, parent_scope: Arc<RwLock<Scope
                     let current_name = target_for.read().unwrap().get_name();
                     target_for.write().unwrap().set_name(format!("{}_for{}", current_name, for_evaluation_count));
                 },
-                TypedValue::Array(_) => (),
+                TypedValue::Array(array) => {
+                    //nets in an array, e.g. the nets expanded from a port bundle net
+                    for element in array {
+                        if let TypedValue::Net(net) = element {
+                            let current_name = net.read().unwrap().get_name();
+                            net.write().unwrap().set_name(format!("{}_for{}", current_name, for_evaluation_count));
+                        }
+                    }
+                },
+                TypedValue::Map(_) => (),
                 TypedValue::Function(_) => (),
+                TypedValue::FunctionDefinition(_) => (),
                 TypedValue::RefToVar(_) => (),
                 TypedValue::Identifier(_) => unreachable!(),
             }
-            existing_array.push(var_value);
+            match &var_value {
+                //nets expanded from a bundle net or a bulk net are flattened, so the nets outside of "for" stay a flat array
+                TypedValue::Array(array) if !array.is_empty() && array.iter().all(|element| matches!(element, TypedValue::Net(_))) => existing_array.extend(array.clone()),
+                _ => existing_array.push(var_value),
+            }
             outside_var.write().unwrap().set_value(TypedValue::Array(existing_array));
         }
 
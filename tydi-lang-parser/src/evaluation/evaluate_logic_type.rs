@@ -1,44 +1,44 @@
 use std::sync::{RwLock, Arc};
-use crate::generate_name::generate_init_value;
+use crate::generate_name::{generate_init_value, get_evaluated_src_tag};
 use crate::tydi_lang_src_to_memory_representation;
-use crate::tydi_memory_representation::{GetScope, LogicBit, LogicGroup, LogicStream, LogicType, LogicUnion, Scope, ScopeType, SrcInfo, TraitCodeLocationAccess, TypeIndication, TypedValue};
+use crate::tydi_memory_representation::{GetScope, LogicBit, LogicGroup, LogicStream, LogicType, LogicUnion, Scope, ScopeType, SrcInfo, TraitCodeLocationAccess, TypeIndication, TypedValue, Variable};
 use crate::tydi_parser::*;
 use crate::error::TydiLangError;
 
-use super::{Evaluator, evaluate_var, evaluate_scope, ScopeOwner};
+use super::{Evaluator, evaluate_var, evaluate_scope, resolve_logic_type_var, ScopeOwner};
 
 #[allow(non_snake_case)]
 pub fn evaluate_LogicalType(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
     evaluator.write().unwrap().increase_deepth();
     
     let mut output = TypedValue::UnknwonValue;
+    let src_info = SrcInfo::new_init_with_tag(get_evaluated_src_tag(src.as_str(), scope.clone()));
     for element in src.clone().into_inner().into_iter() {
         let rule = element.as_rule();
-        match rule {
-            Rule::LogicalType_Basic => {
-                let logic_type = tydi_lang_src_to_memory_representation::parse_LogicalType_Basic(element, scope.clone(), SrcInfo::new_init())?;
-                match &logic_type {
-                    TypeIndication::LogicNull => {
-                        output = TypedValue::LogicTypeValue(Arc::new(RwLock::new(LogicType::LogicNullType)));
-                    },
-                    TypeIndication::LogicStream(var) => {
-                        evaluate_var(var.clone(), scope.clone(), evaluator.clone())?;
-                        output = TypedValue::RefToVar(var.clone());
-                    },
-                    TypeIndication::LogicBit(var) => {
-                        evaluate_var(var.clone(), scope.clone(), evaluator.clone())?;
-                        output = TypedValue::RefToVar(var.clone());
-                    },
-                    TypeIndication::LogicGroup(_) => {
-                        unreachable!()
-                    },
-                    TypeIndication::LogicUnion(_) => {
-                        unreachable!()
-                    },
-                    _ => unreachable!(),
-                }
-            }
+        let logic_type = match rule {
+            Rule::LogicalType_Basic => tydi_lang_src_to_memory_representation::parse_LogicalType_Basic(element, scope.clone(), src_info.clone())?,
+            Rule::LogicalType_Array => tydi_lang_src_to_memory_representation::parse_LogicalType_Array(element, scope.clone(), src_info.clone())?,
             _ => unreachable!()
+        };
+        match &logic_type {
+            TypeIndication::LogicNull => {
+                output = TypedValue::LogicTypeValue(Arc::new(RwLock::new(LogicType::LogicNullType)));
+            },
+            TypeIndication::LogicStream(var) => {
+                evaluate_var(var.clone(), scope.clone(), evaluator.clone())?;
+                output = TypedValue::RefToVar(var.clone());
+            },
+            TypeIndication::LogicBit(var) => {
+                evaluate_var(var.clone(), scope.clone(), evaluator.clone())?;
+                output = TypedValue::RefToVar(var.clone());
+            },
+            TypeIndication::LogicGroup(_) => {
+                unreachable!()
+            },
+            TypeIndication::LogicUnion(_) => {
+                unreachable!()
+            },
+            _ => unreachable!(),
         }
     }
 
@@ -46,6 +46,25 @@ pub fn evaluate_LogicalType(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, evaluato
     return Ok(output);
 }
 
+/// expand the value of a logic type variable to an array if it is declared as an array, e.g. Bit(8)[4]
+pub fn evaluate_logic_type_array(var: Arc<RwLock<Variable>>, value: TypedValue, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+    let array_size_var = match var.read().unwrap().get_array_size() {
+        Some(array_size_var) => array_size_var,
+        None => return Ok(value),
+    };
+    let array_size_value = evaluate_var(array_size_var.clone(), scope.clone(), evaluator.clone())?;
+    let array_size = match &array_size_value {
+        TypedValue::IntValue(x) => {
+            if *x <= 0 {
+                return Err(TydiLangError::new(format!("the size of a logic type array must be larger than 0, found {}", x), array_size_var.read().unwrap().get_code_location()));
+            }
+            *x as usize
+        },
+        _ => return Err(TydiLangError::new(format!("the size of a logic type array must be an integer, found {}", array_size_value.get_brief_info()), array_size_var.read().unwrap().get_code_location())),
+    };
+    return Ok(TypedValue::Array(vec![value; array_size]));
+}
+
 #[allow(non_snake_case)]
 pub fn evaluate_LogicStream(target: Arc<RwLock<LogicStream>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
     evaluator.write().unwrap().increase_deepth();
@@ -66,7 +85,11 @@ pub fn evaluate_LogicStream(target: Arc<RwLock<LogicStream>>, scope: Arc<RwLock<
             },
             TypedValue::RefToVar(ref_var) => {
                 evaluate_var(ref_var.clone(), scope.clone(), evaluator.clone())?;
+                if let TypedValue::Array(_) = resolve_logic_type_var(ref_var.clone()).read().unwrap().get_value() {
+                    return Err(TydiLangError::new(format!("the stream_type cannot be a logic type array, wrap it in a Group"), target.read().unwrap().get_code_location()));
+                }
             }
+            TypedValue::Array(_) => return Err(TydiLangError::new(format!("the stream_type cannot be a logic type array, wrap it in a Group"), target.read().unwrap().get_code_location())),
             _ => return Err(TydiLangError::new(format!("the stream_type {:?} must be a logic type", value), target.read().unwrap().get_code_location()))
         }
     }
@@ -101,7 +124,11 @@ pub fn evaluate_LogicStream(target: Arc<RwLock<LogicStream>>, scope: Arc<RwLock<
             },
             TypedValue::RefToVar(ref_var) => {
                 evaluate_var(ref_var.clone(), scope.clone(), evaluator.clone())?;
+                if let TypedValue::Array(_) = resolve_logic_type_var(ref_var.clone()).read().unwrap().get_value() {
+                    return Err(TydiLangError::new(format!("the user_type cannot be a logic type array, wrap it in a Group"), target.read().unwrap().get_code_location()));
+                }
             }
+            TypedValue::Array(_) => return Err(TydiLangError::new(format!("the user_type cannot be a logic type array, wrap it in a Group"), target.read().unwrap().get_code_location())),
             _ => return Err(TydiLangError::new(format!("the user_type {:?} must be a logic type", value), target.read().unwrap().get_code_location()))
         }
     }
@@ -5,7 +5,7 @@ use crate::tydi_memory_representation::{Streamlet, Scope, TypedValue, TypeIndica
 
 use crate::error::TydiLangError;
 
-use crate::evaluation::{Evaluator, evaluate_port, evaluate_instance, evaluate_net, evaluate_var, evaluate_for};
+use crate::evaluation::{Evaluator, evaluate_port, evaluate_port_bundle, evaluate_instance, evaluate_net, evaluate_var, evaluate_for};
 
 use super::evaluate_if;
 
@@ -84,6 +84,31 @@ pub fn evaluate_scope(target_scope: Arc<RwLock<Scope>>, scope_type: &ScopeType,
                 ScopeOwner::Streamlet(v) => v,
                 _ => unreachable!(),
             };
+            //port bundles are expanded first, so that their ports are evaluated as ordinary ports
+            let variables = streamlet_scope.read().unwrap().get_variables();
+            for (_, var) in variables {
+                let var_type = var.read().unwrap().get_type_indication();
+                match &var_type {
+                    TypeIndication::AnyPortBundle => (),
+                    _ => continue,  //skip this variable
+                }
+                if var.read().unwrap().get_evaluated() == EvaluationStatus::Evaluated {
+                    continue;   //the ports of this bundle are already expanded
+                }
+
+                let port_bundle_typed_value = var.read().unwrap().get_value();
+                let port_bundle = match &port_bundle_typed_value {
+                    TypedValue::PortBundle(port_bundle) => port_bundle.clone(),
+                    _ => unreachable!("something wrong on the parser side, the value should be a port bundle")
+                };
+                let output_value = evaluate_port_bundle(port_bundle.clone(), parent_streamlet.clone(), streamlet_scope.clone(), scope.clone(), evaluator.clone())?;
+                {
+                    let mut var_write = var.write().unwrap();
+                    var_write.set_value(output_value.clone());
+                    var_write.set_evaluated(EvaluationStatus::Evaluated);
+                }
+            }
+
             //for streamlet, we only evaluate ports
             let variables = streamlet_scope.read().unwrap().get_variables();
             for (_, var) in variables {
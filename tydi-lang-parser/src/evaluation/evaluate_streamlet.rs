@@ -1,26 +1,152 @@
 use std::sync::{Arc, RwLock};
+use std::collections::BTreeMap;
 
-use crate::tydi_memory_representation::{Streamlet, Scope, TypedValue, GetScope, Port};
+use crate::deep_clone::DeepClone;
+use crate::trait_common::GetName;
+use crate::tydi_memory_representation::{Streamlet, Scope, TypedValue, GetScope, Port, PortBundle, TraitCodeLocationAccess, ScopeRelationType, GlobalIdentifier};
 
 use crate::error::TydiLangError;
 
-use super::{Evaluator, evaluate_var, evaluate_scope, ScopeOwner};
+use super::{Evaluator, evaluate_var, evaluate_scope, resolve_logic_type_var, get_port_brief_name, ScopeOwner};
 
 
 pub fn evaluate_streamlet(target: Arc<RwLock<Streamlet>>, _scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
     let streamlet_scope = target.read().unwrap().get_scope();
 
+    //a streamlet that extends another streamlet gets a copy of its ports and can access its variables
+    let extends_streamlet_var = target.read().unwrap().get_extends_streamlet_var();
+    let extends_streamlet = target.read().unwrap().get_extends_streamlet();
+    if let (Some(extends_streamlet_var), None) = (extends_streamlet_var, extends_streamlet) {
+        let extends_streamlet_value = evaluate_var(extends_streamlet_var.clone(), streamlet_scope.clone(), evaluator.clone())?;
+        let extends_streamlet = match &extends_streamlet_value {
+            TypedValue::Streamlet(s) => s.clone(),
+            _ => return Err(TydiLangError::new(format!("{} is not a streamlet, but streamlet({}) extends it", extends_streamlet_value.get_brief_info(), target.read().unwrap().get_name()), extends_streamlet_var.read().unwrap().get_code_location())),
+        };
+        inherit_ports(target.clone(), extends_streamlet.clone())?;
+        {
+            let extends_streamlet_scope = extends_streamlet.read().unwrap().get_scope();
+            streamlet_scope.write().unwrap().add_scope_relationship(extends_streamlet_scope, ScopeRelationType::StreamletExtendsRela)?;
+        }
+        target.write().unwrap().set_extends_streamlet(Some(extends_streamlet));
+    }
+
     evaluate_scope(streamlet_scope.clone(), &crate::tydi_memory_representation::ScopeType::StreamletScope, &ScopeOwner::Streamlet(target.clone()), streamlet_scope.clone(), evaluator.clone())?;
 
     return Ok(TypedValue::Streamlet(target));
 }
 
+fn inherit_ports(target: Arc<RwLock<Streamlet>>, extends_streamlet: Arc<RwLock<Streamlet>>) -> Result<(), TydiLangError> {
+    let streamlet_scope = target.read().unwrap().get_scope();
+    let extends_streamlet_vars = extends_streamlet.read().unwrap().get_scope().read().unwrap().get_variables();
+    for (var_name, var) in extends_streamlet_vars {
+        //ports declared in a for loop form an array
+        let is_port = match var.read().unwrap().get_value() {
+            TypedValue::Port(_) => true,
+            TypedValue::Array(elements) => elements.len() > 0 && elements.iter().all(|element| matches!(element, TypedValue::Port(_))),
+            _ => false,
+        };
+        if !is_port {
+            continue;
+        }
+        let existing_var = streamlet_scope.read().unwrap().get_variables_ref().get(&var_name).cloned();
+        if let Some(existing_var) = existing_var {
+            return Err(TydiLangError::new_multiple_locations(format!("port {} of streamlet {} conflicts with the port inherited from streamlet {}", var_name, target.read().unwrap().get_name(), extends_streamlet.read().unwrap().get_name()), vec![existing_var.read().unwrap().get_code_location(), var.read().unwrap().get_code_location()]));
+        }
+
+        let inherited_var = Arc::new(RwLock::new(var.read().unwrap().deep_clone()));
+        let inherited_ports = match inherited_var.read().unwrap().get_value() {
+            TypedValue::Port(port) => vec![port],
+            TypedValue::Array(elements) => elements.into_iter().filter_map(|element| match element { TypedValue::Port(port) => Some(port), _ => None }).collect(),
+            _ => unreachable!(),
+        };
+        for port in inherited_ports {
+            let mut port_write = port.write().unwrap();
+            port_write.set_parent_streamlet(Some(target.clone()));
+            port_write.set_parent_scope(Some(streamlet_scope.clone()));
+        }
+        streamlet_scope.write().unwrap().add_var(inherited_var)?;
+    }
+    return Ok(());
+}
+
+pub fn evaluate_port_bundle(port_bundle: Arc<RwLock<PortBundle>>, parent_streamlet: Arc<RwLock<Streamlet>>, bundle_scope: Arc<RwLock<Scope>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+    let port_bundle_name = port_bundle.read().unwrap().get_name();
+    let interface_var = port_bundle.read().unwrap().get_interface_var();
+    let interface_value = evaluate_var(interface_var.clone(), scope.clone(), evaluator.clone())?;
+    let interface = match &interface_value {
+        TypedValue::Streamlet(s) if s.read().unwrap().get_is_interface() => s.clone(),
+        _ => return Err(TydiLangError::new(format!("{} is not an interface, but port bundle {} uses it", interface_value.get_brief_info(), port_bundle_name), interface_var.read().unwrap().get_code_location())),
+    };
+
+    //each port of the interface becomes an ordinary port named "{bundle}_{port}"
+    let flipped = port_bundle.read().unwrap().get_flipped();
+    let port_bundle_attributes = port_bundle.read().unwrap().get_attributes();
+    let mut bundle_ports = BTreeMap::new();
+    let interface_vars = interface.read().unwrap().get_scope().read().unwrap().get_variables();
+    for (var_name, var) in interface_vars {
+        match var.read().unwrap().get_value() {
+            TypedValue::Port(_) => (),
+            TypedValue::Array(elements) if elements.iter().any(|element| matches!(element, TypedValue::Port(_))) => {
+                return Err(TydiLangError::new_multiple_locations(format!("port array {} of interface {} cannot be used in port bundle {}", var_name, interface.read().unwrap().get_name(), port_bundle_name), vec![port_bundle.read().unwrap().get_code_location(), var.read().unwrap().get_code_location()]));
+            },
+            _ => continue,
+        }
+
+        let bundle_port_var_name = format!("{}_{}", port_bundle_name, var_name);
+        let existing_var = bundle_scope.read().unwrap().get_variables_ref().get(&bundle_port_var_name).cloned();
+        if let Some(existing_var) = existing_var {
+            return Err(TydiLangError::new_multiple_locations(format!("port {} expanded from port bundle {} conflicts with an existing variable in streamlet {}", bundle_port_var_name, port_bundle_name, parent_streamlet.read().unwrap().get_name()), vec![existing_var.read().unwrap().get_code_location(), port_bundle.read().unwrap().get_code_location()]));
+        }
+
+        let bundle_port_var = Arc::new(RwLock::new(var.read().unwrap().deep_clone()));
+        let bundle_port = match bundle_port_var.read().unwrap().get_value() {
+            TypedValue::Port(port) => port,
+            _ => unreachable!(),
+        };
+        {
+            let mut bundle_port_write = bundle_port.write().unwrap();
+            if flipped {
+                let direction = bundle_port_write.get_direction();
+                bundle_port_write.set_direction(direction.flip());
+            }
+            let mut attributes = bundle_port_write.get_attributes();
+            for attribute in &port_bundle_attributes {
+                if !attributes.contains(attribute) {
+                    attributes.push(attribute.clone());
+                }
+            }
+            bundle_port_write.set_attributes(attributes);
+            bundle_port_write.set_name(bundle_port_var_name.clone());
+            bundle_port_write.set_id_in_scope(Some(bundle_port_var_name.clone()));
+            bundle_port_write.set_parent_streamlet(Some(parent_streamlet.clone()));
+            bundle_port_write.set_parent_scope(Some(bundle_scope.clone()));
+        }
+        {
+            let mut bundle_port_var_write = bundle_port_var.write().unwrap();
+            bundle_port_var_write.set_name(bundle_port_var_name.clone());
+            bundle_port_var_write.set_code_location(port_bundle.read().unwrap().get_code_location());
+        }
+        bundle_scope.write().unwrap().add_var(bundle_port_var)?;
+        bundle_ports.insert(var_name, bundle_port);
+    }
+    port_bundle.write().unwrap().set_ports(bundle_ports);
+
+    return Ok(TypedValue::PortBundle(port_bundle));
+}
+
 pub fn evaluate_port(port: Arc<RwLock<Port>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
     let time_domain_var = port.read().unwrap().get_time_domain();
     evaluate_var(time_domain_var.clone(), scope.clone(), evaluator.clone())?;
 
     let logic_type = port.read().unwrap().get_logical_type();
-    evaluate_var(logic_type.clone(), scope.clone(), evaluator.clone())?;
+    let logic_type_value = evaluate_var(logic_type.clone(), scope.clone(), evaluator.clone())?;
+    let logic_type_value = match logic_type_value {
+        TypedValue::RefToVar(var) => resolve_logic_type_var(var).read().unwrap().get_value(),
+        _ => logic_type_value,
+    };
+    if let TypedValue::Array(_) = logic_type_value {
+        return Err(TydiLangError::new(format!("the logic type of port {} cannot be a logic type array, select an element by index", get_port_brief_name(port.clone())), port.read().unwrap().get_code_location()));
+    }
 
     return Ok(TypedValue::Port(port));
 }
@@ -1,7 +1,7 @@
 use std::collections::{BTreeMap, HashSet};
 use std::sync::{Arc, RwLock};
 
-use crate::evaluation::{evaluate_LogicBit, evaluate_LogicGroup, evaluate_LogicUnion, evaluate_LogicStream, evaluate_expression, evaluate_function};
+use crate::evaluation::{resolve_logic_type_var, evaluate_logic_type_array, evaluate_LogicBit, evaluate_LogicGroup, evaluate_LogicUnion, evaluate_LogicStream, evaluate_expression, evaluate_function, convert_template_arg_value};
 use crate::trait_common::GetName;
 use crate::tydi_memory_representation::{CodeLocation, IdentifierType, Variable, TypedValue, Scope, EvaluationStatus, TraitCodeLocationAccess, TypeIndication, LogicType, ScopeRelationType, Function, function};
 use crate::error::TydiLangError;
@@ -12,6 +12,11 @@ pub fn evaluate_value_with_identifier_type(id_name: &String, id_value: TypedValu
     match &id_type {
         IdentifierType::FunctionExp(_) => unreachable!(),  //function expression should be evaluated before this point
         IdentifierType::IndexExp(index_exp) => {
+            //a logic type array is referred by its type variable, e.g. x = Bit(8)[4]
+            let id_value = match &id_value {
+                TypedValue::RefToVar(var) => resolve_logic_type_var(var.clone()).read().unwrap().get_value(),
+                _ => id_value,
+            };
             if let TypedValue::Array(array) = id_value {  //get array value
                 let value = evaluate_expression(index_exp.clone(), None, scope.clone(), evaluator.clone())?;
                 if let TypedValue::IntValue(index_int) = value {    //get index value
@@ -28,8 +33,21 @@ pub fn evaluate_value_with_identifier_type(id_name: &String, id_value: TypedValu
                     return Err(TydiLangError::new(format!("array index expression {{{}}} is not an integer, value: {}", index_exp, value.get_brief_info()), crate::tydi_memory_representation::CodeLocation::new_unknown()));
                 }
             }
+            else if let TypedValue::Map(map) = id_value {   //get map value
+                let value = evaluate_expression(index_exp.clone(), None, scope.clone(), evaluator.clone())?;
+                let value = evaluate_id_in_typed_value(value, None, ScopeRelationType::resolve_id_default(), None, scope.clone(), evaluator.clone())?;
+                if let TypedValue::StringValue(key) = value {   //get key value
+                    match map.get(&key) {
+                        Some(map_value) => return Ok(map_value.clone()),
+                        None => return Err(TydiLangError::new(format!("map {{{}}} doesn't have key \"{}\", available keys: {}", id_name, key, map.keys().cloned().collect::<Vec<_>>().join(", ")), crate::tydi_memory_representation::CodeLocation::new_unknown())),
+                    }
+                }
+                else {
+                    return Err(TydiLangError::new(format!("map key expression {{{}}} is not a string, value: {}", index_exp, value.get_brief_info()), crate::tydi_memory_representation::CodeLocation::new_unknown()));
+                }
+            }
             else {
-                return Err(TydiLangError::new(format!("identifier {{{}}} is not an array, value: {}", id_name, id_value.get_brief_info()), crate::tydi_memory_representation::CodeLocation::new_unknown()));
+                return Err(TydiLangError::new(format!("identifier {{{}}} is not an array or a map, value: {}", id_name, id_value.get_brief_info()), crate::tydi_memory_representation::CodeLocation::new_unknown()));
             }
         },
         IdentifierType::IdentifierExp => {
@@ -61,14 +79,15 @@ pub fn evaluate_id_in_typed_value(id_in_typed_value: TypedValue, location: Optio
                             None => (),
                         }
                     }
-                    output_value = evaluate_function(func.clone(), scope.clone(), evaluator.clone())?;
+                    output_value = evaluate_function(func.clone(), scope.clone(), scope.clone(), evaluator.clone())?;
                 },
                 IdentifierType::IndexExp(_) | IdentifierType::IdentifierExp => {
                     let id_name = id.read().unwrap().get_id();
                     let id_type = id.read().unwrap().get_id_type();
                     let id_template_args = id.read().unwrap().get_template_args();
                     let id_template_arg_exps = evaluate_template_exps_of_var(&id_template_args, scope.clone(), evaluator.clone())?;
-                    let (id_var, id_var_scope) = Scope::resolve_identifier(&id_name, &id_template_arg_exps, &CodeLocation::new_unknown(), scope.clone(), scope.clone(), relationships, evaluator.clone())?;
+                    let id_named_template_arg_exps = evaluate_named_template_exps_of_var(&id.read().unwrap().get_named_template_args(), scope.clone(), evaluator.clone())?;
+                    let (id_var, id_var_scope) = Scope::resolve_identifier_with_named_template_exps(&id_name, &id_template_arg_exps, &id_named_template_arg_exps, &location.clone().unwrap_or(CodeLocation::new_unknown()), scope.clone(), scope.clone(), relationships, evaluator.clone())?;
                     let id_typed_value = evaluate_var(id_var.clone(), id_var_scope.clone(), evaluator.clone())?;
                     output_value = evaluate_value_with_identifier_type(&id_name, id_typed_value, id_type, scope.clone(), evaluator.clone())?;
                     match original_var.clone() {
@@ -104,6 +123,28 @@ pub fn evaluate_template_exps_of_var(var_template_expression: &BTreeMap<usize, S
     return Ok(Some(template_values));
 }
 
+pub fn evaluate_named_template_exps_of_var(var_named_template_expression: &BTreeMap<String, String>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<BTreeMap<String, TypedValue>, TydiLangError> {
+    let mut template_values = BTreeMap::new();
+    for (arg_name, template_exp) in var_named_template_expression {
+        let template_exp_value = evaluate_expression(template_exp.clone(), None, scope.clone(), evaluator.clone())?;
+        template_values.insert(arg_name.clone(), template_exp_value);
+    }
+    return Ok(template_values);
+}
+
+fn check_typed_array(var_name: &String, element_type: &TypeIndication, value: &TypedValue, location: CodeLocation) -> Result<(), TydiLangError> {
+    let elements = match value {
+        TypedValue::Array(elements) => elements,
+        _ => return Err(TydiLangError::new(format!("variable {} is declared as an array of {}, get {}", var_name, element_type.to_string(), value.get_brief_info()), location)),
+    };
+    for (index, element) in elements.iter().enumerate() {
+        if !element_type.is_compatible_with_typed_value(element) {
+            return Err(TydiLangError::new(format!("element {} of variable {} should be {}, get {}", index, var_name, element_type.to_string(), element.get_brief_info()), location));
+        }
+    }
+    return Ok(());
+}
+
 pub fn evaluate_var(var: Arc<RwLock<Variable>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
     #[allow(unused_assignments)]
     let mut output_value = TypedValue::UnknwonValue;
@@ -196,12 +237,27 @@ pub fn evaluate_var(var: Arc<RwLock<Variable>>, scope: Arc<RwLock<Scope>>, evalu
     else if let TypeIndication::LogicTypeRef(logic_ref) = type_indication {
         let var_code_location = var.read().unwrap().get_code_location();
         let mut real_logic_type = evaluate_expression(logic_ref, Some(var_code_location), scope.clone(), evaluator.clone())?;
+        //real_logic_type could be a function call returning a logic type, e.g. field_type(T, "data")
+        let is_function_call = match &real_logic_type {
+            TypedValue::Identifier(identifier) => matches!(identifier.read().unwrap().get_id_type(), IdentifierType::FunctionExp(_)),
+            _ => false,
+        };
+        if is_function_call {
+            let var_code_location = var.read().unwrap().get_code_location();
+            real_logic_type = evaluate_id_in_typed_value(real_logic_type, Some(var_code_location), ScopeRelationType::resolve_id_default(), None, scope.clone(), evaluator.clone())?;
+            {
+                let mut var_write = var.write().unwrap();
+                var_write.set_value(real_logic_type.clone());
+                var_write.set_evaluated(EvaluationStatus::Evaluated);
+            }
+        }
         //real_logic_type could be evaluated to an identifier
-        if let TypedValue::Identifier(identifier) = real_logic_type {
+        else if let TypedValue::Identifier(identifier) = real_logic_type {
             let id: String = identifier.read().unwrap().get_id();
             let template_args = identifier.read().unwrap().get_template_args();
             let template_exps = evaluate_template_exps_of_var(&template_args, scope.clone(), evaluator.clone())?;
-            let (logic_type, logic_type_scope) = Scope::resolve_identifier(&id, &template_exps, &CodeLocation::new_unknown(), scope.clone(), scope.clone(), ScopeRelationType::resolve_id_default(), evaluator.clone())?;
+            let named_template_exps = evaluate_named_template_exps_of_var(&identifier.read().unwrap().get_named_template_args(), scope.clone(), evaluator.clone())?;
+            let (logic_type, logic_type_scope) = Scope::resolve_identifier_with_named_template_exps(&id, &template_exps, &named_template_exps, &var.read().unwrap().get_code_location(), scope.clone(), scope.clone(), ScopeRelationType::resolve_id_default(), evaluator.clone())?;
             real_logic_type = evaluate_var(logic_type.clone(), logic_type_scope.clone(), evaluator.clone())?;
             {
                 let mut var_write = var.write().unwrap();
@@ -214,6 +270,12 @@ pub fn evaluate_var(var: Arc<RwLock<Variable>>, scope: Arc<RwLock<Scope>>, evalu
                 var_write.set_evaluated(EvaluationStatus::Evaluated);
             }
         }
+        //real_logic_type is already a logic type, e.g. the result of a conditional expression
+        else {
+            let mut var_write = var.write().unwrap();
+            var_write.set_value(real_logic_type.clone());
+            var_write.set_evaluated(EvaluationStatus::Evaluated);
+        }
 
         output_value = real_logic_type;
     }
@@ -271,6 +333,9 @@ pub fn evaluate_var(var: Arc<RwLock<Variable>>, scope: Arc<RwLock<Scope>>, evalu
                 output_value = var.read().unwrap().get_value();
             },
         }
+        //expand the logic type to an array, e.g. Bit(8)[4]
+        output_value = evaluate_logic_type_array(var.clone(), output_value, scope.clone(), evaluator.clone())?;
+        var.write().unwrap().set_value(output_value.clone());
     }
 
     //this is a logic type but we don't know exactly which type it is, probably it's a var reference 
@@ -438,7 +503,7 @@ pub fn evaluate_var(var: Arc<RwLock<Variable>>, scope: Arc<RwLock<Scope>>, evalu
     }
 
     // we know this variable is of a basic type, so we evaluate it's expression
-    else if type_indication == TypeIndication::String || type_indication == TypeIndication::Int || type_indication == TypeIndication::Float || type_indication == TypeIndication::Bool {
+    else if type_indication == TypeIndication::String || type_indication == TypeIndication::Int || type_indication == TypeIndication::Float || type_indication == TypeIndication::Bool || type_indication == TypeIndication::Map {
         let var_exp = var.read().unwrap().get_exp();
         match var_exp {
             Some(exp) => {  //evaluate the expression
@@ -458,6 +523,51 @@ pub fn evaluate_var(var: Arc<RwLock<Variable>>, scope: Arc<RwLock<Scope>>, evalu
         }
     }
 
+    // a clock domain is declared with its name, e.g. "clk: clock = \"clk_a\";"
+    else if type_indication == TypeIndication::Clockdomain {
+        let var_exp = var.read().unwrap().get_exp();
+        match var_exp {
+            Some(exp) => {
+                let var_code_location = var.read().unwrap().get_code_location();
+                output_value = evaluate_expression(exp.clone(), Some(var_code_location.clone()), scope.clone(), evaluator.clone())?;
+                output_value = evaluate_id_in_typed_value(output_value, Some(var_code_location), ScopeRelationType::resolve_id_default(), Some(var.clone()), scope.clone(), evaluator.clone())?;
+                if let TypedValue::StringValue(v) = output_value {
+                    output_value = TypedValue::ClockDomainValue(v);
+                }
+                {
+                    let mut var_write = var.write().unwrap();
+                    var_write.set_value(output_value.clone());
+                    var_write.set_evaluated(EvaluationStatus::Evaluated);
+                }
+            },
+            None => {
+                return Err(TydiLangError::new(format!("clock domain variable {} has no value", var.read().unwrap().get_name()), var.read().unwrap().get_code_location()));
+            },
+        }
+    }
+
+    // a typed array is checked element by element, e.g. "widths: [int] = [8, 16];"
+    else if let TypeIndication::Array(element_type) = &type_indication {
+        let var_exp = var.read().unwrap().get_exp();
+        match var_exp {
+            Some(exp) => {
+                let var_code_location = var.read().unwrap().get_code_location();
+                output_value = evaluate_expression(exp.clone(), Some(var_code_location.clone()), scope.clone(), evaluator.clone())?;
+                output_value = evaluate_id_in_typed_value(output_value, Some(var_code_location.clone()), ScopeRelationType::resolve_id_default(), Some(var.clone()), scope.clone(), evaluator.clone())?;
+                output_value = convert_template_arg_value(element_type, output_value);
+                check_typed_array(&var_name, element_type, &output_value, var_code_location)?;
+                {
+                    let mut var_write = var.write().unwrap();
+                    var_write.set_value(output_value.clone());
+                    var_write.set_evaluated(EvaluationStatus::Evaluated);
+                }
+            },
+            None => {
+                return Err(TydiLangError::new(format!("typed array variable {} has no value", var.read().unwrap().get_name()), var.read().unwrap().get_code_location()));
+            },
+        }
+    }
+
     else {
         todo!()
     }
@@ -1,7 +1,8 @@
 use std::sync::{Arc, RwLock};
 
-use crate::tydi_memory_representation::{TypedValue, Project};
+use crate::tydi_memory_representation::{TypedValue, Project, CodeLocation};
 use crate::generate_get_pub;
+use crate::error::TydiLangError;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum EvaluationTraceType {
@@ -110,9 +111,50 @@ impl EvaluationRecord {
 }
 
 
+#[derive(Clone, Debug, PartialEq)]
+pub enum DiagnosticLevel {
+    Print,
+    Warning,
+    Error,
+}
+
+/// a message reported by the source code during evaluation, e.g. warn("deprecated")
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    pub level: DiagnosticLevel,
+    pub message: String,
+    pub location: CodeLocation,
+}
+
+impl Diagnostic {
+    pub fn new(level: DiagnosticLevel, message: String, location: CodeLocation) -> Self {
+        let output = Self {
+            level: level,
+            message: message,
+            location: location,
+        };
+        return output;
+    }
+
+    pub fn print(&self) -> String {
+        let level = match self.level {
+            DiagnosticLevel::Print => "print",
+            DiagnosticLevel::Warning => "warning",
+            DiagnosticLevel::Error => "error",
+        };
+        let mut output = format!("[{}] {}\n", level, self.message);
+        if self.location.begin.is_some() {
+            output.push_str(&format!("{}\n", self.location.show(Some(self.location.src_file.clone()))));
+        }
+        return output;
+    }
+}
+
 pub struct Evaluator {
     project: Arc<RwLock<Project>>,
     evaluation_record: EvaluationRecord,
+    function_call_deepth: usize,
+    diagnostics: Vec<Diagnostic>,
 }
 
 impl Evaluator {
@@ -120,6 +162,8 @@ impl Evaluator {
         let output = Self {
             project: project,
             evaluation_record: EvaluationRecord::new(),
+            function_call_deepth: 0,
+            diagnostics: vec![],
         };
         return Arc::new(RwLock::new(output));
     }
@@ -140,6 +184,14 @@ impl Evaluator {
         self.evaluation_record.decrease_deepth();
     }
 
+    pub fn increase_function_call_deepth(&mut self){
+        self.function_call_deepth += 1;
+    }
+
+    pub fn decrease_function_call_deepth(&mut self){
+        self.function_call_deepth -= 1;
+    }
+
     pub fn print_evaluation_record(&self) -> String {
         let evaluation_record = &self.evaluation_record.traces;
         let mut output = String::new();
@@ -149,6 +201,31 @@ impl Evaluator {
         return output;
     }
 
+    pub fn add_diagnostic(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    pub fn print_diagnostics(&self) -> String {
+        let mut output = String::new();
+        for diagnostic in &self.diagnostics {
+            output.push_str(&diagnostic.print());
+        }
+        return output;
+    }
+
+    /// the error diagnostics fail the evaluation after the target is evaluated, so all of them are reported at once
+    pub fn check_error_diagnostics(&self) -> Result<(), TydiLangError> {
+        let errors: Vec<&Diagnostic> = self.diagnostics.iter().filter(|diagnostic| diagnostic.level == DiagnosticLevel::Error).collect();
+        if errors.len() == 0 {
+            return Ok(());
+        }
+        let messages: Vec<String> = errors.iter().map(|diagnostic| diagnostic.message.clone()).collect();
+        let locations: Vec<CodeLocation> = errors.iter().map(|diagnostic| diagnostic.location.clone()).collect();
+        return Err(TydiLangError::new_multiple_locations(format!("{} error(s) reported during evaluation:\n{}", errors.len(), messages.join("\n")), locations));
+    }
+
     generate_get_pub!(evaluation_record, EvaluationRecord, get_evaluation_record);
+    generate_get_pub!(diagnostics, Vec<Diagnostic>, get_diagnostics);
     generate_get_pub!(project, Arc<RwLock<Project>>, get_project);
+    generate_get_pub!(function_call_deepth, usize, get_function_call_deepth);
 }
\ No newline at end of file
@@ -73,6 +73,248 @@ mod test_expression_parser {
         check_exp(format!("[1.0,2.0,\"3.0\",4.0] == [1.0,2.0] + \"3.0\" + 4.0"), TypedValue::BoolValue(true));
     }
 
+    #[test]
+    fn array_range() {
+        let ints = |v: Vec<i128>| TypedValue::Array(v.into_iter().map(|i| TypedValue::IntValue(i)).collect());
+        check_exp(format!("[0..4]"), ints(vec![0,1,2,3]));
+        check_exp(format!("[0..=4]"), ints(vec![0,1,2,3,4]));
+        check_exp(format!("[1 .. 10 : 3]"), ints(vec![1,4,7]));
+        check_exp(format!("[1..=10:3]"), ints(vec![1,4,7,10]));
+        check_exp(format!("[4..0:-1]"), ints(vec![4,3,2,1]));
+        check_exp(format!("[4..=0:-2]"), ints(vec![4,2,0]));
+        check_exp(format!("[2..2]"), ints(vec![]));
+        check_exp(format!("[1+1..2*3]"), ints(vec![2,3,4,5]));
+        check_exp(format!("[0..2] + [5..=6]"), ints(vec![0,1,5,6]));
+        check_exp(format!("[0.5, 1.]"), TypedValue::Array(vec![TypedValue::FloatValue(0.5), TypedValue::FloatValue(1.0)]));
+
+        let scope = Scope::new_place_holder();
+        let evaluator = Evaluator::new(Project::new(format!("test")));
+        let error = evaluate_expression(format!("[0..4.0]"), None, scope.clone(), evaluator.clone()).expect_err("float bound should be rejected");
+        assert!(error.message.contains("end of range"));
+        let error = evaluate_expression(format!("[0..4:0]"), None, scope.clone(), evaluator.clone()).expect_err("zero step should be rejected");
+        assert!(error.message.contains("cannot be 0"));
+        let location = &error.location[0];
+        assert_eq!(&location.src_file.file_content[location.begin.unwrap()..location.end.unwrap()], "[0..4:0]", "the error points to the range");
+        let error = evaluate_expression(format!("[0..=170141183460469231731687303715884105727:170141183460469231731687303715884105727]"), None, scope.clone(), evaluator.clone()).expect_err("overflow should be rejected");
+        assert!(error.message.contains("overflows"), "{}", error.message);
+        let error = evaluate_expression(format!("[0..{}]", crate::evaluation::MAX_ARRAY_RANGE_SIZE + 1), None, scope.clone(), evaluator.clone()).expect_err("a too large range should be rejected");
+        assert!(error.message.contains("more than"), "{}", error.message);
+        check_exp(format!("[0..{}]", crate::evaluation::MAX_ARRAY_RANGE_SIZE), ints((0..crate::evaluation::MAX_ARRAY_RANGE_SIZE as i128).collect()));
+    }
+
+    fn evaluate_function_exp(exp: String) -> Result<TypedValue, crate::error::TydiLangError> {
+        let scope = Scope::new_place_holder();
+        let evaluator = Evaluator::new(Project::new(format!("test")));
+        let output = evaluate_expression(exp, None, scope.clone(), evaluator.clone())?;
+        return evaluate_id_in_typed_value(output, None, crate::tydi_memory_representation::ScopeRelationType::resolve_id_default(), None, scope.clone(), evaluator.clone());
+    }
+
+    fn check_function_error(exp: String, message: &str) {
+        let error = evaluate_function_exp(exp.clone()).expect_err(&format!("{} should fail", exp));
+        assert!(error.message.contains(message), "{}", error.message);
+    }
+
+    #[test]
+    fn math_functions() {
+        let check = |exp: &str, val: TypedValue| assert_eq!(evaluate_function_exp(exp.to_string()).expect("evaluation fail"), val);
+        check("clog2(1)", TypedValue::IntValue(0));
+        check("clog2(5)", TypedValue::IntValue(3));
+        check("clog2(8)", TypedValue::IntValue(3));
+        check("clog2(9)", TypedValue::IntValue(4));
+        check("log2(8)", TypedValue::IntValue(3));
+        check("log2(9)", TypedValue::IntValue(3));
+        check("log2(8.0)", TypedValue::FloatValue(3.0));
+        check("pow(2, 10)", TypedValue::IntValue(1024));
+        check("pow(2, 0)", TypedValue::IntValue(1));
+        check("pow(2.0, -1)", TypedValue::FloatValue(0.5));
+        check("pow(4, 0.5)", TypedValue::FloatValue(2.0));
+        check("min(3, 1, 2)", TypedValue::IntValue(1));
+        check("max(3, 1, 2)", TypedValue::IntValue(3));
+        check("max([4, 8, 2])", TypedValue::IntValue(8));
+        check("min(3, 1.5)", TypedValue::FloatValue(1.5));
+        check("abs(-3)", TypedValue::IntValue(3));
+        check("abs(-2.5)", TypedValue::FloatValue(2.5));
+        check("ceil(2.1)", TypedValue::IntValue(3));
+        check("floor(2.9)", TypedValue::IntValue(2));
+        check("round(2.5)", TypedValue::IntValue(3));
+        check("round(-2.5)", TypedValue::IntValue(-3));
+        check("ceil(7)", TypedValue::IntValue(7));
+        check("clog2(max(16, 20)) + 1", TypedValue::IntValue(6));
+
+        check_function_error(format!("clog2(0)"), "must be positive");
+        check_function_error(format!("clog2(2.0)"), "can only be an integer");
+        check_function_error(format!("log2(-1.0)"), "must be positive");
+        check_function_error(format!("pow(2)"), "pow function has 2 argument(s), get 1");
+        check_function_error(format!("pow(2, 127)"), "overflows");
+        check_function_error(format!("pow(2, -1)"), "cannot be negative");
+        check_function_error(format!("pow(\"2\", 1)"), "argument 0 of pow");
+        check_function_error(format!("abs(-170141183460469231731687303715884105727 - 1)"), "overflows");
+        check_function_error(format!("max()"), "at least one value");
+        check_function_error(format!("max(1, true)"), "argument 1 of max");
+        check_function_error(format!("min(4)"), "an array when it is the only argument");
+        check_function_error(format!("floor(pow(10.0, 40))"), "overflows");
+    }
+
+    #[test]
+    fn array_functions() {
+        let check = |exp: &str, val: &str| assert_eq!(evaluate_function_exp(exp.to_string()).expect("evaluation fail"), get_exp_value(val.to_string()));
+        check("len([1, 2, 3])", "3");
+        check("len(slice([1], 1))", "0");
+        check("len(range(8))", "8");
+        check("slice([1, 2, 3, 4], 1, 3)", "[2, 3]");
+        check("slice([1, 2, 3, 4], 2)", "[3, 4]");
+        check("len(slice([1, 2], 1, 1))", "0");
+        check("reverse([1, 2, 3])", "[3, 2, 1]");
+        check("contains([1, 2, 3], 2)", "true");
+        check("contains([\"a\", \"b\"], \"c\")", "false");
+        check("contains(slice([1], 1), 1)", "false");
+        check("index_of([4, 5, 6], 6)", "2");
+        check("index_of([4, 5, 6], 7)", "-1");
+        check("zip([1, 2], [\"a\", \"b\"])", "[[1, \"a\"], [2, \"b\"]]");
+        check("flatten([[1, 2], slice([0], 1), [3]])", "[1, 2, 3]");
+        check("flatten(zip([1, 2], [3, 4]))", "[1, 3, 2, 4]");
+        check("sum([1, 2, 3])", "6");
+        check("sum([0.5, 1.5])", "2.0");
+        check("sum(slice([1.5], 1))", "0");
+        check("unique([3, 1, 3, 2, 1])", "[3, 1, 2]");
+        check("sum(slice(reverse(range(5)), 0, len([1, 2])))", "7");
+
+        check_function_error(format!("len(1)"), "argument 0 of len can only be an array");
+        check_function_error(format!("len([1], [2])"), "len function has 1 argument(s), get 2");
+        check_function_error(format!("slice([1, 2], 1, 3)"), "out of the array range");
+        check_function_error(format!("slice([1, 2], 2, 1)"), "out of the array range");
+        check_function_error(format!("slice([1, 2])"), "2 or 3 arguments");
+        check_function_error(format!("slice([1, 2], 0.5)"), "argument 1 of slice can only be an integer");
+        check_function_error(format!("contains([1, 2.0], 1)"), "same type, get int and float");
+        check_function_error(format!("contains([1, 2], 1.0)"), "searches a float in an array of int");
+        check_function_error(format!("index_of([true], 1)"), "searches a int in an array of bool");
+        check_function_error(format!("zip([1, 2], [1])"), "same length, get 2 and 1");
+        check_function_error(format!("flatten([[1], 2])"), "every element to be an array");
+        check_function_error(format!("sum([1, 2.0])"), "same type, get int and float");
+        check_function_error(format!("sum([\"a\"])"), "argument 0 of sum can only be an array of integers or floats");
+        check_function_error(format!("unique([1, \"1\"])"), "same type, get int and string");
+    }
+
+    #[test]
+    fn string_functions() {
+        let check = |exp: &str, val: &str| assert_eq!(evaluate_function_exp(exp.to_string()).expect("evaluation fail"), get_exp_value(val.to_string()));
+        check(r#"format("lane_{}", 2)"#, r#""lane_2""#);
+        check(r#"format("{}_{}x{}", "clk", 1.5, true)"#, r#""clk_1.5xtrue""#);
+        check(r#"format("{{}} {}", 1)"#, r#""{} 1""#);
+        check(r#"format("no placeholder")"#, r#""no placeholder""#);
+        check(r#"split("a,b,,c", ",")"#, r#"["a", "b", "", "c"]"#);
+        check(r#"join(["a", "b", "c"], "_")"#, r#""a_b_c""#);
+        check(r#"join(split("a.b", "."), "::")"#, r#""a::b""#);
+        check(r#"replace("clk_a_a", "_a", "_b")"#, r#""clk_b_b""#);
+        check(r#"upper("clk_a")"#, r#""CLK_A""#);
+        check(r#"lower("CLK_A")"#, r#""clk_a""#);
+        check(r#"starts_with("clk_100", "clk")"#, "true");
+        check(r#"starts_with("clk", "clk_100")"#, "false");
+        check(r#"substring("stream", 1, 3)"#, r#""tr""#);
+        check(r#"substring("stream", 3)"#, r#""eam""#);
+
+        check_function_error(format!(r#"format("{{}} {{}}", 1)"#), "more placeholders than the 1 value(s) given");
+        check_function_error(format!(r#"format("{{}}", 1, 2)"#), "has 1 placeholder(s), get 2 value(s)");
+        check_function_error(format!(r#"format("a{{b", 1)"#), "unmatched brace");
+        check_function_error(format!(r#"format("{{}}", [1])"#), "argument 1 of format can only be a basic value");
+        check_function_error(format!(r#"format(1)"#), "argument 0 of format can only be a string");
+        check_function_error(format!(r#"split("a", "")"#), "argument 1 of split cannot be an empty string");
+        check_function_error(format!(r#"join(["a", 1], "")"#), "join requires an array of strings");
+        check_function_error(format!(r#"replace("a", "", "b")"#), "cannot be an empty string");
+        check_function_error(format!(r#"upper(1)"#), "argument 0 of upper can only be a string");
+        check_function_error(format!(r#"starts_with("a")"#), "starts_with function has 2 argument(s), get 1");
+        check_function_error(format!(r#"substring("abc", 2, 4)"#), "out of the string range");
+    }
+
+    #[test]
+    fn conversion_functions() {
+        let check = |exp: &str, val: &str| assert_eq!(evaluate_function_exp(exp.to_string()).expect("evaluation fail"), get_exp_value(val.to_string()));
+        check("int(2.7)", "2");
+        check("int(-2.7)", "-2");
+        check(r#"int(2.5, "round")"#, "3");
+        check(r#"int(2.1, "ceil")"#, "3");
+        check(r#"int(-2.1, "floor")"#, "-3");
+        check(r#"int(2.9, "trunc")"#, "2");
+        check("int(true) + int(5)", "6");
+        check(r#"int("42")"#, "42");
+        check(r#"int(" -8 ")"#, "-8");
+        check(r#"int("0x1F") + int("0b11") + int("1_000")"#, "1034");
+        check("float(3)", "3.0");
+        check(r#"float("2.5") * 2"#, "5.0");
+        check(r#"float("7")"#, "7.0");
+        check(r#"bool("true") && !bool(" false")"#, "true");
+        check("bool(false)", "false");
+        check(r#"int(float("2.5"), "round") + len(range(int("3")))"#, "6");
+
+        check_function_error(format!(r#"int("4a")"#), r#"int cannot parse "4a" as an integer"#);
+        check_function_error(format!(r#"int("2.5")"#), "as an integer");
+        check_function_error(format!(r#"int("")"#), "as an integer");
+        check_function_error(format!(r#"int("- 1")"#), "as an integer");
+        check_function_error(format!(r#"int("0x1000000000000000000000000000000000")"#), "overflows");
+        check_function_error(format!(r#"int(2.5, "up")"#), "unknown rounding mode");
+        check_function_error(format!("int(pow(10.0, 40))"), "overflows");
+        check_function_error(format!("int([1])"), "argument 0 of int can only be an integer, a float, a bool or a string");
+        check_function_error(format!("int()"), "int function has 1 or 2 arguments, get 0");
+        check_function_error(format!(r#"float("inf")"#), "as a float");
+        check_function_error(format!(r#"float("x")"#), "as a float");
+        check_function_error(format!("float(true)"), "argument 0 of float");
+        check_function_error(format!(r#"bool("yes")"#), "only \"true\" and \"false\"");
+        check_function_error(format!("bool(1)"), "argument 0 of bool can only be a bool or a string");
+    }
+
+    #[test]
+    fn conditional_expressions() {
+        let check = |exp: &str, val: &str| assert_eq!(evaluate_function_exp(exp.to_string()).expect("evaluation fail"), get_exp_value(val.to_string()));
+        check("true ? 1 : 2", "1");
+        check("1 > 2 ? 1 : 2", "2");
+        check("1 + 1 == 2 ? 3 * 2 : 0", "6");
+        check("(false ? 1 : 2) + 10", "12");
+        check("true ? false ? 1 : 2 : 3", "2");
+        check("false ? 1 : false ? 2 : 3", "3");
+        check("if false then 1 else 2", "2");
+        check("if 2 > 1 then \"a\" else \"b\"", "\"a\"");
+        check("if false then 1 else if true then 2 else 3", "2");
+        check("(if true then 1 else 2) + (true ? 10 : 20)", "11");
+        check("if !false then len(range(3)) else 0", "3");
+        //the branch which is not taken is never evaluated
+        check("true ? 1 : int(\"x\")", "1");
+        check("if false then int(\"x\") else 2", "2");
+
+        check_function_error(format!("1 ? 1 : 2"), "the condition of a conditional expression must be a bool, get");
+        check_function_error(format!("if \"true\" then 1 else 2"), "must be a bool");
+        check_function_error(format!("false ? 1 : int(\"x\")"), "int cannot parse");
+    }
+
+    #[test]
+    fn array_comprehensions() {
+        let check = |exp: &str, val: &str| assert_eq!(evaluate_function_exp(exp.to_string()).expect("evaluation fail"), get_exp_value(val.to_string()));
+        check("[i * 2 for i in range(4)]", "[0, 2, 4, 6]");
+        check("[i for i in [1..=10] if i % 3 == 0]", "[3, 6, 9]");
+        check("[upper(s) for s in [\"a\", \"b\"]]", "[\"A\", \"B\"]");
+        check("[i > 1 ? i : 0 for i in range(3)]", "[0, 0, 2]");
+        check("[[j for j in range(i)] for i in [1..3]]", "[[0], [0, 1]]");
+        check("len([i for i in range(5) if i > 10])", "0");
+        check("sum([i for i in range(5)]) + len([1 for x in [1, 2]])", "12");
+        check("[i for i in [1, 2] if (i > 1) == true]", "[2]");
+
+        check_function_error(format!("[i for i in 3]"), "the range of array comprehension [i for i in 3] can only be an array or a map, get Int(3)");
+        check_function_error(format!("[i for i in range(2) if i]"), "the filter of array comprehension [i for i in range(2) if i] can only be a bool, get Int(0)");
+        check_function_error(format!("[int(s) for s in [\"1\", \"y\"]]"), "int cannot parse");
+    }
+
+    #[test]
+    fn map_values() {
+        let check = |exp: &str, val: &str| assert_eq!(evaluate_function_exp(exp.to_string()).expect("evaluation fail"), get_exp_value(val.to_string()));
+        check("{width: 32, \"depth\": 2 * 512} == {depth: 1024, width: 32}", "true");
+        check("{width: 32} == {width: 16}", "false");
+        check("{width: 32} == {depth: 32}", "false");
+        check("[k for k in {b: 1, a: 2, c: 3}]", "[\"a\", \"b\", \"c\"]");
+        check("{a: {b: [1, 2]}} == {a: {b: [1, 2]}}", "true");
+
+        check_function_error(format!("{{a: 1, \"a\": 2}}"), "duplicated key \"a\" in map");
+        check_function_error(format!("{{a: 1}} == 1"), "LogicalEq not supported");
+    }
+
     #[test]
     fn assert_eq_values() {
         assert!(get_exp_value(format!("1+2*9")) == get_exp_value(format!("19")));
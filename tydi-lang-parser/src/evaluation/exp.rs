@@ -3,11 +3,11 @@ use std::sync::{RwLock, Arc};
 use pest::pratt_parser::PrattParser;
 
 use crate::evaluation::evaluate_LogicalType;
-use crate::tydi_memory_representation::{Scope, TypedValue, CodeLocation};
+use crate::tydi_memory_representation::{Scope, TypedValue, CodeLocation, ScopeRelationType};
 use crate::tydi_parser::*;
 use crate::error::TydiLangError;
 
-use super::{Evaluator, evaluate_Term, evaluate_BinaryOperation};
+use super::{Evaluator, evaluate_Term, evaluate_BinaryOperation, evaluate_id_in_typed_value};
 
 #[derive(Clone, Debug)]
 pub enum Expression {
@@ -18,6 +18,13 @@ pub enum Expression {
         op: Operator,
         rhs: Box<Expression>,
     },
+    //the branches are kept as source strings so that only the selected one is evaluated
+    Conditional {
+        condition: Box<Expression>,
+        then_exp: String,
+        else_exp: String,
+        location: Option<CodeLocation>,
+    },
 }
 
 impl Expression {
@@ -39,7 +46,18 @@ impl Expression {
                     },
                     _ => return Ok(value),
                 }
-            } 
+            },
+            Expression::Conditional { condition, then_exp, else_exp, location } => {
+                let condition_value = condition.evaluate_TypedValue(scope.clone(), evaluator.clone())?;
+                let condition_value = evaluate_id_in_typed_value(condition_value, location.clone(), ScopeRelationType::resolve_id_default(), None, scope.clone(), evaluator.clone())?;
+                match condition_value {
+                    TypedValue::BoolValue(v) => {
+                        let selected_exp = if v { then_exp } else { else_exp };
+                        return evaluate_expression(selected_exp.clone(), location.clone(), scope.clone(), evaluator.clone());
+                    },
+                    _ => return Err(TydiLangError::new(format!("the condition of a conditional expression must be a bool, get {}", condition_value.get_brief_info()), CodeLocation::new_unknown())),
+                }
+            },
         }
     }
 }
@@ -105,6 +123,23 @@ lazy_static::lazy_static! {
 }
 
 pub fn evaluate_expression_pest(exp: Pair<Rule>, location: Option<CodeLocation>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<Expression, TydiLangError> {
+    let mut elements: Vec<Pair<Rule>> = exp.into_inner().collect();
+
+    //if c then a else b
+    if elements.len() == 1 && elements[0].as_rule() == Rule::ConditionalIfExp {
+        let mut branches = elements.remove(0).into_inner().filter(|element| element.as_rule() == Rule::Exp);
+        let condition = evaluate_expression_pest(branches.next().unwrap(), location.clone(), scope.clone(), evaluator.clone())?;
+        let then_exp = branches.next().unwrap().as_str().to_string();
+        let else_exp = branches.next().unwrap().as_str().to_string();
+        return Ok(Expression::Conditional { condition: Box::new(condition), then_exp: then_exp, else_exp: else_exp, location: location });
+    }
+
+    //c ? a : b, the condition is everything before the "?"
+    let mut conditional_branches = None;
+    if elements.last().is_some() && elements.last().unwrap().as_rule() == Rule::ConditionalBranches {
+        conditional_branches = elements.pop();
+    }
+
     let result = PRATT_PARSER
     .map_primary(|primary| match primary.as_rule() {
         Rule::Term => {
@@ -161,7 +196,14 @@ pub fn evaluate_expression_pest(exp: Pair<Rule>, location: Option<CodeLocation>,
             rhs: Box::new(rhs),
         }
     })
-    .parse(exp.into_inner());
+    .parse(elements.into_iter());
+
+    if conditional_branches.is_some() {
+        let mut branches = conditional_branches.unwrap().into_inner();
+        let then_exp = branches.next().unwrap().as_str().to_string();
+        let else_exp = branches.next().unwrap().as_str().to_string();
+        return Ok(Expression::Conditional { condition: Box::new(result), then_exp: then_exp, else_exp: else_exp, location: location });
+    }
 
     return Ok(result);
 }
@@ -172,8 +214,8 @@ pub fn evaluate_expression(exp: String, location: Option<CodeLocation>, scope: A
         unreachable!("because the exp should have already been parsed before, we should never reach here");
     }
     let mut parse_result = parse_result.ok().unwrap();
-    let expresssion = evaluate_expression_pest(parse_result.next().unwrap(), location, scope.clone(), evaluator.clone())?;
-    return expresssion.evaluate_TypedValue(scope.clone(), evaluator.clone());
+    let expresssion = evaluate_expression_pest(parse_result.next().unwrap(), location.clone(), scope.clone(), evaluator.clone()).map_err(|e| e.with_default_location(location.clone()))?;
+    return expresssion.evaluate_TypedValue(scope.clone(), evaluator.clone()).map_err(|e| e.with_default_location(location));
 }
 
 
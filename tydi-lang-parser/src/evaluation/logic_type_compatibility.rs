@@ -0,0 +1,233 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+use crate::trait_common::GetName;
+use crate::tydi_memory_representation::{Variable, TypedValue, LogicType, GetScope};
+
+/// follow the RefToVar chain of a variable until we reach the variable holding the real value
+pub fn resolve_logic_type_var(var: Arc<RwLock<Variable>>) -> Arc<RwLock<Variable>> {
+    let mut current_var = var.clone();
+    //a reference chain longer than this is a reference loop, which should have been reported during evaluation
+    for _ in 0..1000 {
+        let value = current_var.read().unwrap().get_value();
+        match value {
+            TypedValue::RefToVar(inner_var) => {
+                if Arc::ptr_eq(&inner_var, &current_var) { break; }
+                current_var = inner_var;
+            },
+            _ => break,
+        }
+    }
+    return current_var;
+}
+
+/// get the name of the type variable that a logic type is declared with, e.g. x in "x = Bit(8);"
+pub fn get_logic_type_var_name(var: Arc<RwLock<Variable>>) -> String {
+    let mut current_var = var.clone();
+    for _ in 0..1000 {
+        if current_var.read().unwrap().get_is_name_user_defined() {
+            return current_var.read().unwrap().get_name();
+        }
+        let alias = current_var.read().unwrap().get_alias();
+        if alias.len() > 0 {
+            return alias[0].clone();
+        }
+        let value = current_var.read().unwrap().get_value();
+        match value {
+            TypedValue::RefToVar(inner_var) => {
+                if Arc::ptr_eq(&inner_var, &current_var) { break; }
+                current_var = inner_var;
+            },
+            _ => break,
+        }
+    }
+    return current_var.read().unwrap().get_name();
+}
+
+fn resolve_typed_value(value: &TypedValue) -> TypedValue {
+    match value {
+        TypedValue::RefToVar(var) => {
+            let real_var = resolve_logic_type_var(var.clone());
+            return real_var.read().unwrap().get_value();
+        },
+        _ => return value.clone(),
+    }
+}
+
+/// strict mode: two logic types are compatible only if they come from the same named type variable
+pub fn check_logic_type_strict(lhs: Arc<RwLock<Variable>>, rhs: Arc<RwLock<Variable>>) -> Option<String> {
+    let lhs_value = resolve_logic_type_var(lhs.clone()).read().unwrap().get_value();
+    let rhs_value = resolve_logic_type_var(rhs.clone()).read().unwrap().get_value();
+    if let (TypedValue::LogicTypeValue(lhs_type), TypedValue::LogicTypeValue(rhs_type)) = (&lhs_value, &rhs_value) {
+        if *lhs_type.read().unwrap() == *rhs_type.read().unwrap() {
+            return None;
+        }
+    }
+    let lhs_name = get_logic_type_var_name(lhs);
+    let rhs_name = get_logic_type_var_name(rhs);
+    if lhs_name != rhs_name {
+        return Some(format!("type variable {} is not {}", lhs_name, rhs_name));
+    }
+    //same name, but the variable might be cloned during template expansion, so they must have the same structure
+    return check_logic_type_value_structural(&lhs_value, &rhs_value, &lhs_name);
+}
+
+/// structural mode: two logic types are compatible if they have the same Bit widths, Group/Union fields and Stream properties
+pub fn check_logic_type_structural(lhs: Arc<RwLock<Variable>>, rhs: Arc<RwLock<Variable>>) -> Option<String> {
+    let lhs_value = resolve_logic_type_var(lhs).read().unwrap().get_value();
+    let rhs_value = resolve_logic_type_var(rhs).read().unwrap().get_value();
+    return check_logic_type_value_structural(&lhs_value, &rhs_value, &format!("type"));
+}
+
+/// return None if two values are structurally equal, otherwise return the reason
+pub fn check_logic_type_value_structural(lhs: &TypedValue, rhs: &TypedValue, path: &String) -> Option<String> {
+    let lhs = resolve_typed_value(lhs);
+    let rhs = resolve_typed_value(rhs);
+    match (&lhs, &rhs) {
+        (TypedValue::LogicTypeValue(lhs_type), TypedValue::LogicTypeValue(rhs_type)) => {
+            let lhs_type = lhs_type.read().unwrap().clone();
+            let rhs_type = rhs_type.read().unwrap().clone();
+            return check_logic_type_structural_inner(&lhs_type, &rhs_type, path);
+        },
+        (TypedValue::Array(lhs_array), TypedValue::Array(rhs_array)) => {
+            if lhs_array.len() != rhs_array.len() {
+                return Some(format!("{}: array size {} is not {}", path, lhs_array.len(), rhs_array.len()));
+            }
+            for i in 0..lhs_array.len() {
+                let result = check_logic_type_value_structural(&lhs_array[i], &rhs_array[i], &format!("{}[{}]", path, i));
+                if result.is_some() {
+                    return result;
+                }
+            }
+            return None;
+        },
+        (lhs, rhs) => {
+            if lhs == rhs {
+                return None;
+            }
+            return Some(format!("{}: {} is not {}", path, lhs.get_brief_info(), rhs.get_brief_info()));
+        },
+    }
+}
+
+fn check_logic_type_structural_inner(lhs: &LogicType, rhs: &LogicType, path: &String) -> Option<String> {
+    if lhs == rhs {
+        return None;
+    }
+    match (lhs, rhs) {
+        (LogicType::LogicNullType, LogicType::LogicNullType) => return None,
+        (LogicType::LogicBitType(lhs_bit), LogicType::LogicBitType(rhs_bit)) => {
+            let lhs_width = lhs_bit.read().unwrap().get_bit_width().read().unwrap().get_value();
+            let rhs_width = rhs_bit.read().unwrap().get_bit_width().read().unwrap().get_value();
+            return check_logic_type_value_structural(&lhs_width, &rhs_width, &format!("{}(Bit).width", path));
+        },
+        (LogicType::LogicGroupType(lhs_group), LogicType::LogicGroupType(rhs_group)) => {
+            let lhs_scope = lhs_group.read().unwrap().get_scope();
+            let rhs_scope = rhs_group.read().unwrap().get_scope();
+            let lhs_fields = get_logic_type_fields(lhs_scope.read().unwrap().get_variables());
+            let rhs_fields = get_logic_type_fields(rhs_scope.read().unwrap().get_variables());
+            return check_logic_type_fields(lhs_fields, rhs_fields, &format!("{}(Group)", path));
+        },
+        (LogicType::LogicUnionType(lhs_union), LogicType::LogicUnionType(rhs_union)) => {
+            let lhs_scope = lhs_union.read().unwrap().get_scope();
+            let rhs_scope = rhs_union.read().unwrap().get_scope();
+            let lhs_fields = get_logic_type_fields(lhs_scope.read().unwrap().get_variables());
+            let rhs_fields = get_logic_type_fields(rhs_scope.read().unwrap().get_variables());
+            return check_logic_type_fields(lhs_fields, rhs_fields, &format!("{}(Union)", path));
+        },
+        (LogicType::LogicStreamType(lhs_stream), LogicType::LogicStreamType(rhs_stream)) => {
+            let lhs_stream = lhs_stream.read().unwrap();
+            let rhs_stream = rhs_stream.read().unwrap();
+            let properties = vec![
+                (format!("stream_type"), lhs_stream.get_stream_type(), rhs_stream.get_stream_type()),
+                (format!("dimension"), lhs_stream.get_dimension(), rhs_stream.get_dimension()),
+                (format!("user_type"), lhs_stream.get_user_type(), rhs_stream.get_user_type()),
+                (format!("throughput"), lhs_stream.get_throughput(), rhs_stream.get_throughput()),
+                (format!("synchronicity"), lhs_stream.get_synchronicity(), rhs_stream.get_synchronicity()),
+                (format!("complexity"), lhs_stream.get_complexity(), rhs_stream.get_complexity()),
+                (format!("direction"), lhs_stream.get_direction(), rhs_stream.get_direction()),
+                (format!("keep"), lhs_stream.get_keep(), rhs_stream.get_keep()),
+            ];
+            for (property_name, lhs_property, rhs_property) in properties {
+                let lhs_property_value = lhs_property.read().unwrap().get_value();
+                let rhs_property_value = rhs_property.read().unwrap().get_value();
+                let result = check_logic_type_value_structural(&lhs_property_value, &rhs_property_value, &format!("{}(Stream).{}", path, property_name));
+                if result.is_some() {
+                    return result;
+                }
+            }
+            return None;
+        },
+        (lhs, rhs) => {
+            return Some(format!("{}: {} is not {}", path, lhs.get_brief_info(), rhs.get_brief_info()));
+        },
+    }
+}
+
+/// a description of a value that only depends on its structure, structurally equal logic types get the same signature
+pub fn get_logic_type_value_signature(value: &TypedValue) -> String {
+    let value = resolve_typed_value(value);
+    match &value {
+        TypedValue::LogicTypeValue(logic_type) => {
+            let logic_type = logic_type.read().unwrap().clone();
+            return get_logic_type_signature(&logic_type);
+        },
+        TypedValue::Array(array) => return format!("Array({})", array.iter().map(|i| get_logic_type_value_signature(i)).collect::<Vec<_>>().join(", ")),
+        TypedValue::Map(map) => return format!("Map({})", map.iter().map(|(key, value)| format!("{}: {}", key, get_logic_type_value_signature(value))).collect::<Vec<_>>().join(", ")),
+        _ => return value.get_brief_info(),
+    }
+}
+
+fn get_logic_type_signature(logic_type: &LogicType) -> String {
+    match logic_type {
+        LogicType::LogicNullType => return format!("Null"),
+        LogicType::LogicBitType(bit) => {
+            let width = bit.read().unwrap().get_bit_width().read().unwrap().get_value();
+            return format!("Bit({})", get_logic_type_value_signature(&width));
+        },
+        LogicType::LogicGroupType(group) => {
+            let fields = get_logic_type_fields(group.read().unwrap().get_scope().read().unwrap().get_variables());
+            return format!("Group({})", get_logic_type_fields_signature(fields));
+        },
+        LogicType::LogicUnionType(union) => {
+            let fields = get_logic_type_fields(union.read().unwrap().get_scope().read().unwrap().get_variables());
+            return format!("Union({})", get_logic_type_fields_signature(fields));
+        },
+        LogicType::LogicStreamType(stream) => {
+            let stream = stream.read().unwrap();
+            let properties = vec![stream.get_stream_type(), stream.get_dimension(), stream.get_user_type(), stream.get_throughput(), stream.get_synchronicity(), stream.get_complexity(), stream.get_direction(), stream.get_keep()];
+            return format!("Stream({})", properties.iter().map(|property| get_logic_type_value_signature(&property.read().unwrap().get_value())).collect::<Vec<_>>().join(", "));
+        },
+    }
+}
+
+fn get_logic_type_fields_signature(fields: BTreeMap<String, Arc<RwLock<Variable>>>) -> String {
+    return fields.iter().map(|(field_name, field)| format!("{}: {}", field_name, get_logic_type_value_signature(&field.read().unwrap().get_value()))).collect::<Vec<_>>().join(", ");
+}
+
+fn get_logic_type_fields(vars: BTreeMap<String, Arc<RwLock<Variable>>>) -> BTreeMap<String, Arc<RwLock<Variable>>> {
+    let mut output = BTreeMap::new();
+    for (var_name, var) in vars {
+        if !var.read().unwrap().get_is_property_of_scope() { continue; }
+        output.insert(var_name, var);
+    }
+    return output;
+}
+
+fn check_logic_type_fields(lhs_fields: BTreeMap<String, Arc<RwLock<Variable>>>, rhs_fields: BTreeMap<String, Arc<RwLock<Variable>>>, path: &String) -> Option<String> {
+    for (field_name, _) in lhs_fields.iter().chain(rhs_fields.iter()) {
+        if !lhs_fields.contains_key(field_name) || !rhs_fields.contains_key(field_name) {
+            return Some(format!("{}: field {} does not exist in both types", path, field_name));
+        }
+    }
+    for (field_name, lhs_field) in &lhs_fields {
+        let rhs_field = &rhs_fields[field_name];
+        let lhs_field_value = lhs_field.read().unwrap().get_value();
+        let rhs_field_value = rhs_field.read().unwrap().get_value();
+        let result = check_logic_type_value_structural(&lhs_field_value, &rhs_field_value, &format!("{}.{}", path, field_name));
+        if result.is_some() {
+            return result;
+        }
+    }
+    return None;
+}
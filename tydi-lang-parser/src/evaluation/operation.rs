@@ -1,9 +1,12 @@
 use std::sync::{Arc, RwLock};
 
-use crate::{tydi_memory_representation::{TypedValue, CodeLocation, Scope, ScopeRelationType, GetScope, Variable}, trait_common::AccessProperty};
+use crate::{tydi_memory_representation::{TypedValue, CodeLocation, Scope, ScopeRelationType, GetScope, Variable, Function, IdentifierType}, trait_common::AccessProperty};
 use crate::error::TydiLangError;
 
-use super::{Expression, Operator, Evaluator, evaluate_var, evaluate_id_in_typed_value, evaluate_value_with_identifier_type, evaluate_template_exps_of_var};
+use crate::trait_common::GetName;
+use crate::post_compile::check_clock_domain::get_port_clock_domain;
+
+use super::{Expression, Operator, Evaluator, evaluate_var, resolve_logic_type_var, get_port_brief_name, evaluate_id_in_typed_value, evaluate_value_with_identifier_type, evaluate_template_exps_of_var, evaluate_named_template_exps_of_var, evaluate_function};
 
 
 #[allow(non_snake_case)]
@@ -14,7 +17,10 @@ pub fn evaluate_BinaryOperation(lhs: &Box<Expression>, op: &Operator, rhs: &Box<
             let (value, ref_var) = perform_AccessInner(lhs, rhs, scope.clone(), evaluator.clone())?;
             return Ok((value, ref_var));
         },
-        Operator::AccessProperty => todo!(),
+        Operator::AccessProperty => {
+            let (value, ref_var) = perform_AccessProperty(lhs, rhs, scope.clone(), evaluator.clone())?;
+            return Ok((value, ref_var));
+        },
         Operator::LeftShift => {
             let value = perform_LeftShift(lhs, rhs, scope.clone(), evaluator.clone())?;
             return Ok((value, None));
@@ -177,14 +183,27 @@ pub fn perform_AccessInner(lhs: &Box<Expression>, rhs: &Box<Expression>, scope:
         _ => unreachable!()
     };
 
+    //if the rhs is a function call, it is looked up in the scope of lhs, but the arguments belong to the caller, so they are evaluated in the current scope
+    if let IdentifierType::FunctionExp(function_args) = rhs_var_id.read().unwrap().get_id_type() {
+        let func = Function::new(format!("not_set"));
+        {
+            let mut func_write = func.write().unwrap();
+            func_write.set_function_id(rhs_var_name.clone());
+            func_write.set_function_arg_exps(function_args.clone());
+        }
+        let function_value = evaluate_function(func.clone(), scope_of_rhs_var.clone(), scope.clone(), evaluator.clone())?;
+        return Ok((function_value, None));
+    }
+
     let template_exps = evaluate_template_exps_of_var(&rhs_template_args, scope.clone(), evaluator.clone())?;
-    let (rhs_var, rhs_var_scope) = Scope::resolve_identifier(&rhs_var_name, &template_exps, &CodeLocation::new_unknown(), scope_of_rhs_var.clone(), scope_of_rhs_var.clone(), resolve_var_scope_edge, evaluator.clone())?;
+    let named_template_exps = evaluate_named_template_exps_of_var(&rhs_var_id.read().unwrap().get_named_template_args(), scope.clone(), evaluator.clone())?;
+    let (rhs_var, rhs_var_scope) = Scope::resolve_identifier_with_named_template_exps(&rhs_var_name, &template_exps, &named_template_exps, &CodeLocation::new_unknown(), scope_of_rhs_var.clone(), scope_of_rhs_var.clone(), resolve_var_scope_edge, evaluator.clone())?;
     let rhs_typed_value = evaluate_var(rhs_var.clone(), rhs_var_scope.clone(), evaluator.clone())?;
     //if it is an index expression (an element of an array)
     let iden_type = rhs_var_id.read().unwrap().get_id_type();
     let rhs_typed_value = evaluate_value_with_identifier_type(&rhs_var_name, rhs_typed_value, iden_type.clone(), scope.clone(), evaluator.clone())?;
     match iden_type {
-        crate::tydi_memory_representation::IdentifierType::FunctionExp(_) => todo!(),
+        crate::tydi_memory_representation::IdentifierType::FunctionExp(_) => unreachable!("function calls are evaluated before resolving the identifier"),
         crate::tydi_memory_representation::IdentifierType::IndexExp(_) => {
             return Ok((rhs_typed_value, None));
         },
@@ -195,6 +214,79 @@ pub fn perform_AccessInner(lhs: &Box<Expression>, rhs: &Box<Expression>, scope:
     }
 }
 
+//read a property of a logic type, a port or an instance: e.g. stream->dimension
+#[allow(non_snake_case)]
+pub fn perform_AccessProperty(lhs: &Box<Expression>, rhs: &Box<Expression>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<(TypedValue, Option<Arc<RwLock<Variable>>>), TydiLangError> {
+    let lhs_value = lhs.evaluate_TypedValue(scope.clone(), evaluator.clone())?;
+    let mut lhs_value = evaluate_id_in_typed_value(lhs_value, None, ScopeRelationType::resolve_id_default(), None, scope.clone(), evaluator.clone())?;
+    let rhs_value = rhs.evaluate_TypedValue(scope.clone(), evaluator.clone())?;
+    //get property name
+    let property_name = match &rhs_value {
+        TypedValue::Identifier(iden) => {
+            match iden.read().unwrap().get_id_type() {
+                crate::tydi_memory_representation::IdentifierType::IdentifierExp => iden.read().unwrap().get_id(),
+                _ => return Err(TydiLangError::new(format!("property name {} must be a plain identifier", iden.read().unwrap().get_id()), CodeLocation::new_unknown())),
+            }
+        }
+        _ => return Err(TydiLangError::new(format!("property name must be an identifier, get {}", rhs_value.get_brief_info()), CodeLocation::new_unknown())),
+    };
+
+    //if the lhs value is a reference
+    if let TypedValue::RefToVar(inner_var) = lhs_value.clone() {
+        lhs_value = resolve_logic_type_var(inner_var).read().unwrap().get_value();
+    }
+
+    let (property_var, property_var_scope) = match &lhs_value {
+        TypedValue::LogicTypeValue(v) => {
+            let logic_type = v.read().unwrap();
+            let (property_var, available_properties) = match &*logic_type {
+                crate::tydi_memory_representation::LogicType::LogicNullType => (None, vec![]),
+                crate::tydi_memory_representation::LogicType::LogicBitType(bit) => (bit.read().unwrap().access_porperty(&property_name), crate::tydi_memory_representation::logic_bit::AVAILABLE_PROPERTIES.to_vec()),
+                crate::tydi_memory_representation::LogicType::LogicGroupType(group) => (group.read().unwrap().access_porperty(&property_name), crate::tydi_memory_representation::logic_group::AVAILABLE_PROPERTIES.to_vec()),
+                crate::tydi_memory_representation::LogicType::LogicUnionType(union) => (union.read().unwrap().access_porperty(&property_name), crate::tydi_memory_representation::logic_union::AVAILABLE_PROPERTIES.to_vec()),
+                crate::tydi_memory_representation::LogicType::LogicStreamType(stream) => (stream.read().unwrap().access_porperty(&property_name), crate::tydi_memory_representation::logic_stream::AVAILABLE_PROPERTIES.to_vec()),
+            };
+            match property_var {
+                Some(property_var) => (property_var, scope.clone()),
+                None => return Err(TydiLangError::new(format!("{} doesn't have property {}, available: {:?}", logic_type.get_brief_info(), &property_name, available_properties), CodeLocation::new_unknown())),
+            }
+        },
+        TypedValue::Port(port) => {
+            let property_var = port.read().unwrap().access_porperty(&property_name);
+            let property_var = match property_var {
+                Some(property_var) => property_var,
+                None => return Err(TydiLangError::new(format!("port {} doesn't have property {}, available: {:?}", get_port_brief_name(port.clone()), &property_name, crate::tydi_memory_representation::port::AVAILABLE_PROPERTIES), CodeLocation::new_unknown())),
+            };
+            //the properties of a port are declared in the streamlet scope
+            let port_scope = match port.read().unwrap().get_parent_streamlet() {
+                Some(streamlet) => streamlet.read().unwrap().get_scope(),
+                None => scope.clone(),
+            };
+            //a clock domain might be declared as a string, always return it as a clock domain
+            if Arc::ptr_eq(&property_var, &port.read().unwrap().get_time_domain()) {
+                evaluate_var(property_var.clone(), port_scope.clone(), evaluator.clone())?;
+                return match get_port_clock_domain(port.clone()) {
+                    Some(clock_domain) => Ok((TypedValue::ClockDomainValue(clock_domain), None)),
+                    None => Err(TydiLangError::new(format!("the clock domain of port {} is not evaluated", get_port_brief_name(port.clone())), CodeLocation::new_unknown())),
+                };
+            }
+            (property_var, port_scope)
+        },
+        TypedValue::Instance(inst) => {
+            let property_var = inst.read().unwrap().access_porperty(&property_name);
+            match property_var {
+                Some(property_var) => (property_var, scope.clone()),
+                None => return Err(TydiLangError::new(format!("instance {} doesn't have property {}, available: {:?}", inst.read().unwrap().get_name(), &property_name, crate::tydi_memory_representation::instance::AVAILABLE_PROPERTIES), CodeLocation::new_unknown())),
+            }
+        },
+        _ => return Err(TydiLangError::new(format!("{} doesn't have properties, only logic types, ports and instances have properties", lhs_value.get_brief_info()), CodeLocation::new_unknown())),
+    };
+
+    evaluate_var(property_var.clone(), property_var_scope.clone(), evaluator.clone())?;
+    let property_var = resolve_logic_type_var(property_var);
+    return Ok((property_var.read().unwrap().get_value(), Some(property_var.clone())));
+}
+
 #[allow(non_snake_case)]
 pub fn perform_Add(lhs: &Box<Expression>, rhs: &Box<Expression>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
     let lhs_value = lhs.evaluate_TypedValue(scope.clone(), evaluator.clone())?;
@@ -456,6 +548,9 @@ pub fn perform_LogicalEq(lhs: &Box<Expression>, rhs: &Box<Expression>, scope: Ar
             }
             return Ok(TypedValue::BoolValue(true));
         },
+        (TypedValue::Map(v0), TypedValue::Map(v1)) => {
+            return Ok(TypedValue::BoolValue(v0 == v1));
+        },
 
         (v0, v1) => return Err(TydiLangError::new(format!("LogicalEq not supported for {:?} and {:?}", v0 ,v1), CodeLocation::new_unknown()))
     }
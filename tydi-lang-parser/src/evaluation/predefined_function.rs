@@ -1,8 +1,10 @@
 use std::collections::BTreeMap;
 use std::sync::{Arc, RwLock};
 
-use crate::evaluation::{FunctionTrait, Evaluator, evaluate_expression, evaluate_id_in_typed_value};
-use crate::tydi_memory_representation::{TypedValue, Scope, Function, ScopeRelationType, TraitCodeLocationAccess};
+use crate::evaluation::{FunctionTrait, Evaluator, Diagnostic, DiagnosticLevel, evaluate_expression, evaluate_id_in_typed_value, resolve_logic_type_var, evaluate_IntExp};
+use crate::tydi_parser::{TydiLangSrc, Parser, Rule};
+use crate::trait_common::GetName;
+use crate::tydi_memory_representation::{TypedValue, TypeIndication, Scope, Function, ScopeRelationType, TraitCodeLocationAccess, LogicType, Variable, GetScope};
 use crate::error::TydiLangError;
 
 pub fn get_function(function: Arc<RwLock<Function>>) -> Result<Box<dyn FunctionTrait>, TydiLangError> {
@@ -20,6 +22,158 @@ pub fn get_function(function: Arc<RwLock<Function>>) -> Result<Box<dyn FunctionT
         return Ok(Box::new(FunctionRange{}));
     }
 
+    if function_id == FunctionClog2::get_id() {
+        return Ok(Box::new(FunctionClog2{}));
+    }
+
+    if function_id == FunctionLog2::get_id() {
+        return Ok(Box::new(FunctionLog2{}));
+    }
+
+    if function_id == FunctionPow::get_id() {
+        return Ok(Box::new(FunctionPow{}));
+    }
+
+    if function_id == FunctionMin::get_id() {
+        return Ok(Box::new(FunctionMin{}));
+    }
+
+    if function_id == FunctionMax::get_id() {
+        return Ok(Box::new(FunctionMax{}));
+    }
+
+    if function_id == FunctionAbs::get_id() {
+        return Ok(Box::new(FunctionAbs{}));
+    }
+
+    if function_id == FunctionCeil::get_id() {
+        return Ok(Box::new(FunctionCeil{}));
+    }
+
+    if function_id == FunctionFloor::get_id() {
+        return Ok(Box::new(FunctionFloor{}));
+    }
+
+    if function_id == FunctionRound::get_id() {
+        return Ok(Box::new(FunctionRound{}));
+    }
+
+    if function_id == FunctionLen::get_id() {
+        return Ok(Box::new(FunctionLen{}));
+    }
+
+    if function_id == FunctionSlice::get_id() {
+        return Ok(Box::new(FunctionSlice{}));
+    }
+
+    if function_id == FunctionReverse::get_id() {
+        return Ok(Box::new(FunctionReverse{}));
+    }
+
+    if function_id == FunctionContains::get_id() {
+        return Ok(Box::new(FunctionContains{}));
+    }
+
+    if function_id == FunctionIndexOf::get_id() {
+        return Ok(Box::new(FunctionIndexOf{}));
+    }
+
+    if function_id == FunctionZip::get_id() {
+        return Ok(Box::new(FunctionZip{}));
+    }
+
+    if function_id == FunctionFlatten::get_id() {
+        return Ok(Box::new(FunctionFlatten{}));
+    }
+
+    if function_id == FunctionSum::get_id() {
+        return Ok(Box::new(FunctionSum{}));
+    }
+
+    if function_id == FunctionUnique::get_id() {
+        return Ok(Box::new(FunctionUnique{}));
+    }
+
+    if function_id == FunctionFormat::get_id() {
+        return Ok(Box::new(FunctionFormat{}));
+    }
+
+    if function_id == FunctionSplit::get_id() {
+        return Ok(Box::new(FunctionSplit{}));
+    }
+
+    if function_id == FunctionJoin::get_id() {
+        return Ok(Box::new(FunctionJoin{}));
+    }
+
+    if function_id == FunctionReplace::get_id() {
+        return Ok(Box::new(FunctionReplace{}));
+    }
+
+    if function_id == FunctionUpper::get_id() {
+        return Ok(Box::new(FunctionUpper{}));
+    }
+
+    if function_id == FunctionLower::get_id() {
+        return Ok(Box::new(FunctionLower{}));
+    }
+
+    if function_id == FunctionStartsWith::get_id() {
+        return Ok(Box::new(FunctionStartsWith{}));
+    }
+
+    if function_id == FunctionSubstring::get_id() {
+        return Ok(Box::new(FunctionSubstring{}));
+    }
+
+    if function_id == FunctionBitWidth::get_id() {
+        return Ok(Box::new(FunctionBitWidth{}));
+    }
+
+    if function_id == FunctionFieldNames::get_id() {
+        return Ok(Box::new(FunctionFieldNames{}));
+    }
+
+    if function_id == FunctionFieldType::get_id() {
+        return Ok(Box::new(FunctionFieldType{}));
+    }
+
+    if function_id == FunctionIsStream::get_id() {
+        return Ok(Box::new(FunctionIsStream{}));
+    }
+
+    if function_id == FunctionIsGroup::get_id() {
+        return Ok(Box::new(FunctionIsGroup{}));
+    }
+
+    if function_id == FunctionElementCount::get_id() {
+        return Ok(Box::new(FunctionElementCount{}));
+    }
+
+    if function_id == FunctionInt::get_id() {
+        return Ok(Box::new(FunctionInt{}));
+    }
+
+    if function_id == FunctionFloat::get_id() {
+        return Ok(Box::new(FunctionFloat{}));
+    }
+
+    if function_id == FunctionBool::get_id() {
+        return Ok(Box::new(FunctionBool{}));
+    }
+
+    if function_id == FunctionPrint::get_id() {
+        return Ok(Box::new(FunctionPrint{}));
+    }
+
+    if function_id == FunctionWarn::get_id() {
+        return Ok(Box::new(FunctionWarn{}));
+    }
+
+    if function_id == FunctionError::get_id() {
+        return Ok(Box::new(FunctionError{}));
+    }
+
     return Err(TydiLangError::new(format!("unknown function {} ", function_id), function.read().unwrap().get_code_location()));
 }
 
@@ -99,16 +253,11 @@ impl FunctionTrait for FunctionToString {
         
         if function_args.len() == 1 {
             let arg_value = function_arg_values.get(&0).expect("bug: arg not on index 0");
-            match arg_value {
-                TypedValue::IntValue(v) => return Ok(TypedValue::StringValue(v.to_string())),
-                TypedValue::StringValue(v) => return Ok(TypedValue::StringValue(v.to_string())),
-                TypedValue::BoolValue(v) => return Ok(TypedValue::StringValue(v.to_string())),
-                TypedValue::FloatValue(v) => return Ok(TypedValue::StringValue(v.to_string())),
-                TypedValue::ClockDomainValue(v) => return Ok(TypedValue::StringValue(v.to_string())),
-                _ => return Err(TydiLangError::new(format!("{} argument has value {}, which is not able to be converted to String", function.read().unwrap().get_function_id(), arg_value.get_brief_info()), function.read().unwrap().get_code_location()))
+            match basic_value_to_string(arg_value) {
+                Some(v) => return Ok(TypedValue::StringValue(v)),
+                None => return Err(TydiLangError::new(format!("{} argument has value {}, which is not able to be converted to String", function.read().unwrap().get_function_id(), arg_value.get_brief_info()), function.read().unwrap().get_code_location()))
             }
 
-
         }
         else {
             return Err(TydiLangError::new(format!("toString function has 1 argument, get {}", function_args.len()), function.read().unwrap().get_code_location()));
@@ -195,4 +344,1214 @@ impl FunctionTrait for FunctionRange {
             return Err(TydiLangError::new(format!("range function has 1/2/3 argument, get {}", function_args.len()), function.read().unwrap().get_code_location()));
         }
     }
-}
\ No newline at end of file
+}
+
+fn evaluate_function_args(function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<BTreeMap<usize, TypedValue>, TydiLangError> {
+    let function_args = function.read().unwrap().get_function_arg_exps();
+    let mut function_arg_values = BTreeMap::new();
+    for index in 0 .. function_args.len() {
+        let function_arg = function_args.get(&index).expect("wrong function arg index");
+        let arg_value = evaluate_expression(function_arg.clone(), Some(function.read().unwrap().get_code_location()), scope.clone(), evaluator.clone())?;
+        let arg_value = evaluate_id_in_typed_value(arg_value, Some(function.read().unwrap().get_code_location()), ScopeRelationType::resolve_id_default(), None, scope.clone(), evaluator.clone())?;
+        function_arg_values.insert(index, arg_value);
+    }
+    return Ok(function_arg_values);
+}
+
+fn check_function_arg_count(function: Arc<RwLock<Function>>, function_arg_values: &BTreeMap<usize, TypedValue>, expected_count: usize) -> Result<(), TydiLangError> {
+    if function_arg_values.len() != expected_count {
+        let function_id = function.read().unwrap().get_function_id();
+        return Err(TydiLangError::new(format!("{} function has {} argument(s), get {}", function_id, expected_count, function_arg_values.len()), function.read().unwrap().get_code_location()));
+    }
+    return Ok(());
+}
+
+// converts a float to an int, e.g. for ceil, floor and round
+fn float_to_int(function: Arc<RwLock<Function>>, value: f64) -> Result<TypedValue, TydiLangError> {
+    if !value.is_finite() || value < i128::MIN as f64 || value >= i128::MAX as f64 {
+        return Err(TydiLangError::new(format!("{} overflows: {} cannot be converted to an integer", function.read().unwrap().get_function_id(), value), function.read().unwrap().get_code_location()));
+    }
+    return Ok(TypedValue::IntValue(value as i128));
+}
+
+fn overflow_error(function: Arc<RwLock<Function>>) -> TydiLangError {
+    return TydiLangError::new(format!("{} overflows the integer range", function.read().unwrap().get_function_id()), function.read().unwrap().get_code_location());
+}
+
+fn type_error(function: Arc<RwLock<Function>>, index: usize, expected: &str, value: &TypedValue) -> TydiLangError {
+    return TydiLangError::new(format!("argument {} of {} can only be {}, get {}", index, function.read().unwrap().get_function_id(), expected, value.get_brief_info()), function.read().unwrap().get_code_location());
+}
+
+struct FunctionClog2 {}
+
+impl FunctionClog2 {
+    const ID: &str = "clog2";
+}
+
+impl FunctionTrait for FunctionClog2 {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    // the number of bits to represent n different values, e.g. clog2(5) == 3
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        check_function_arg_count(function.clone(), &function_arg_values, 1)?;
+        let value = function_arg_values.get(&0).expect("bug: arg not on index 0");
+        match value {
+            TypedValue::IntValue(v) => {
+                if *v <= 0 {
+                    return Err(TydiLangError::new(format!("argument of clog2 must be positive, get {}", v), function.read().unwrap().get_code_location()));
+                }
+                if *v == 1 {
+                    return Ok(TypedValue::IntValue(0));
+                }
+                return Ok(TypedValue::IntValue((v - 1).ilog2() as i128 + 1));
+            },
+            _ => return Err(type_error(function.clone(), 0, "an integer", value)),
+        }
+    }
+}
+
+struct FunctionLog2 {}
+
+impl FunctionLog2 {
+    const ID: &str = "log2";
+}
+
+impl FunctionTrait for FunctionLog2 {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    // log2 of an integer is rounded down, e.g. log2(5) == 2
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        check_function_arg_count(function.clone(), &function_arg_values, 1)?;
+        let value = function_arg_values.get(&0).expect("bug: arg not on index 0");
+        match value {
+            TypedValue::IntValue(v) => {
+                if *v <= 0 {
+                    return Err(TydiLangError::new(format!("argument of log2 must be positive, get {}", v), function.read().unwrap().get_code_location()));
+                }
+                return Ok(TypedValue::IntValue(v.ilog2() as i128));
+            },
+            TypedValue::FloatValue(v) => {
+                if *v <= 0.0 {
+                    return Err(TydiLangError::new(format!("argument of log2 must be positive, get {}", v), function.read().unwrap().get_code_location()));
+                }
+                return Ok(TypedValue::FloatValue(v.log2()));
+            },
+            _ => return Err(type_error(function.clone(), 0, "an integer or a float", value)),
+        }
+    }
+}
+
+struct FunctionPow {}
+
+impl FunctionPow {
+    const ID: &str = "pow";
+}
+
+impl FunctionTrait for FunctionPow {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        check_function_arg_count(function.clone(), &function_arg_values, 2)?;
+        let base = function_arg_values.get(&0).expect("bug: arg not on index 0");
+        let exponent = function_arg_values.get(&1).expect("bug: arg not on index 1");
+        match (base, exponent) {
+            (TypedValue::IntValue(base), TypedValue::IntValue(exponent)) => {
+                if *exponent < 0 {
+                    return Err(TydiLangError::new(format!("the exponent of an integer pow cannot be negative, get {}, use a float base instead", exponent), function.read().unwrap().get_code_location()));
+                }
+                let exponent = u32::try_from(*exponent).map_err(|_| overflow_error(function.clone()))?;
+                let value = base.checked_pow(exponent).ok_or(overflow_error(function.clone()))?;
+                return Ok(TypedValue::IntValue(value));
+            },
+            (TypedValue::IntValue(_) | TypedValue::FloatValue(_), TypedValue::IntValue(_) | TypedValue::FloatValue(_)) => {
+                let to_float = |v: &TypedValue| match v {
+                    TypedValue::IntValue(i) => *i as f64,
+                    TypedValue::FloatValue(f) => *f,
+                    _ => unreachable!(),
+                };
+                return Ok(TypedValue::FloatValue(to_float(base).powf(to_float(exponent))));
+            },
+            (TypedValue::IntValue(_) | TypedValue::FloatValue(_), _) => return Err(type_error(function.clone(), 1, "an integer or a float", exponent)),
+            _ => return Err(type_error(function.clone(), 0, "an integer or a float", base)),
+        }
+    }
+}
+
+// min and max accept several numbers or a single array of numbers, the result is a float if any number is a float
+fn find_min_max(function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>, find_max: bool) -> Result<TypedValue, TydiLangError> {
+    let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+    let values: Vec<TypedValue> = match (function_arg_values.len(), function_arg_values.get(&0)) {
+        (1, Some(TypedValue::Array(array))) => array.clone(),
+        (1, Some(value)) => return Err(type_error(function.clone(), 0, "an array when it is the only argument", value)),
+        _ => function_arg_values.values().cloned().collect(),
+    };
+    if values.len() == 0 {
+        return Err(TydiLangError::new(format!("{} requires at least one value", function.read().unwrap().get_function_id()), function.read().unwrap().get_code_location()));
+    }
+
+    let mut has_float = false;
+    for (index, value) in values.iter().enumerate() {
+        match value {
+            TypedValue::IntValue(_) => (),
+            TypedValue::FloatValue(_) => has_float = true,
+            _ => return Err(type_error(function.clone(), index, "an integer or a float", value)),
+        }
+    }
+
+    if has_float {
+        let floats = values.iter().map(|v| match v {
+            TypedValue::IntValue(i) => *i as f64,
+            TypedValue::FloatValue(f) => *f,
+            _ => unreachable!(),
+        });
+        let output = if find_max { floats.fold(f64::MIN, f64::max) } else { floats.fold(f64::MAX, f64::min) };
+        return Ok(TypedValue::FloatValue(output));
+    }
+    else {
+        let ints = values.iter().map(|v| match v {
+            TypedValue::IntValue(i) => *i,
+            _ => unreachable!(),
+        });
+        let output = if find_max { ints.max() } else { ints.min() };
+        return Ok(TypedValue::IntValue(output.expect("bug: values should not be empty")));
+    }
+}
+
+struct FunctionMin {}
+
+impl FunctionMin {
+    const ID: &str = "min";
+}
+
+impl FunctionTrait for FunctionMin {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        return find_min_max(function, scope, evaluator, false);
+    }
+}
+
+struct FunctionMax {}
+
+impl FunctionMax {
+    const ID: &str = "max";
+}
+
+impl FunctionTrait for FunctionMax {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        return find_min_max(function, scope, evaluator, true);
+    }
+}
+
+struct FunctionAbs {}
+
+impl FunctionAbs {
+    const ID: &str = "abs";
+}
+
+impl FunctionTrait for FunctionAbs {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        check_function_arg_count(function.clone(), &function_arg_values, 1)?;
+        let value = function_arg_values.get(&0).expect("bug: arg not on index 0");
+        match value {
+            TypedValue::IntValue(v) => return Ok(TypedValue::IntValue(v.checked_abs().ok_or(overflow_error(function.clone()))?)),
+            TypedValue::FloatValue(v) => return Ok(TypedValue::FloatValue(v.abs())),
+            _ => return Err(type_error(function.clone(), 0, "an integer or a float", value)),
+        }
+    }
+}
+
+// ceil, floor and round convert a float to an integer, an integer is returned as it is
+fn round_to_int(function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>, rounding: fn(f64) -> f64) -> Result<TypedValue, TydiLangError> {
+    let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+    check_function_arg_count(function.clone(), &function_arg_values, 1)?;
+    let value = function_arg_values.get(&0).expect("bug: arg not on index 0");
+    match value {
+        TypedValue::IntValue(v) => return Ok(TypedValue::IntValue(*v)),
+        TypedValue::FloatValue(v) => return float_to_int(function.clone(), rounding(*v)),
+        _ => return Err(type_error(function.clone(), 0, "an integer or a float", value)),
+    }
+}
+
+struct FunctionCeil {}
+
+impl FunctionCeil {
+    const ID: &str = "ceil";
+}
+
+impl FunctionTrait for FunctionCeil {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        return round_to_int(function, scope, evaluator, f64::ceil);
+    }
+}
+
+struct FunctionFloor {}
+
+impl FunctionFloor {
+    const ID: &str = "floor";
+}
+
+impl FunctionTrait for FunctionFloor {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        return round_to_int(function, scope, evaluator, f64::floor);
+    }
+}
+
+struct FunctionRound {}
+
+impl FunctionRound {
+    const ID: &str = "round";
+}
+
+impl FunctionTrait for FunctionRound {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        return round_to_int(function, scope, evaluator, f64::round);
+    }
+}
+
+fn get_array_arg(function: Arc<RwLock<Function>>, function_arg_values: &BTreeMap<usize, TypedValue>, index: usize) -> Result<Vec<TypedValue>, TydiLangError> {
+    let value = function_arg_values.get(&index).expect("bug: arg index out of range");
+    match value {
+        TypedValue::Array(array) => return Ok(array.clone()),
+        _ => return Err(type_error(function.clone(), index, "an array", value)),
+    }
+}
+
+fn get_int_arg(function: Arc<RwLock<Function>>, function_arg_values: &BTreeMap<usize, TypedValue>, index: usize) -> Result<i128, TydiLangError> {
+    let value = function_arg_values.get(&index).expect("bug: arg index out of range");
+    match value {
+        TypedValue::IntValue(v) => return Ok(*v),
+        _ => return Err(type_error(function.clone(), index, "an integer", value)),
+    }
+}
+
+// comparing values is only meaningful for basic values, e.g. two ports are not comparable
+fn check_comparable_value(function: Arc<RwLock<Function>>, value: &TypedValue) -> Result<(), TydiLangError> {
+    match value {
+        TypedValue::IntValue(_) | TypedValue::FloatValue(_) | TypedValue::StringValue(_) | TypedValue::BoolValue(_) | TypedValue::ClockDomainValue(_) => return Ok(()),
+        TypedValue::Array(array) => {
+            for element in array {
+                check_comparable_value(function.clone(), element)?;
+            }
+            return Ok(());
+        },
+        _ => return Err(TydiLangError::new(format!("{} can only compare basic values, get {}", function.read().unwrap().get_function_id(), value.get_brief_info()), function.read().unwrap().get_code_location())),
+    }
+}
+
+// all elements of the array must have the same type, e.g. [1, 2.0] is rejected
+fn check_same_element_type(function: Arc<RwLock<Function>>, array: &Vec<TypedValue>) -> Result<(), TydiLangError> {
+    if array.len() == 0 {
+        return Ok(());
+    }
+    let first_type = TypeIndication::infer_from_typed_value(&array[0]);
+    for element in array {
+        let element_type = TypeIndication::infer_from_typed_value(element);
+        if element_type != first_type {
+            return Err(TydiLangError::new(format!("{} requires all elements to have the same type, get {} and {}", function.read().unwrap().get_function_id(), first_type.to_string(), element_type.to_string()), function.read().unwrap().get_code_location()));
+        }
+    }
+    return Ok(());
+}
+
+// searching requires the value to have the same type as the elements
+fn find_in_array(function: Arc<RwLock<Function>>, function_arg_values: &BTreeMap<usize, TypedValue>) -> Result<Option<usize>, TydiLangError> {
+    check_function_arg_count(function.clone(), function_arg_values, 2)?;
+    let array = get_array_arg(function.clone(), function_arg_values, 0)?;
+    let value = function_arg_values.get(&1).expect("bug: arg not on index 1");
+    check_same_element_type(function.clone(), &array)?;
+    check_comparable_value(function.clone(), value)?;
+    if array.len() != 0 {
+        let element_type = TypeIndication::infer_from_typed_value(&array[0]);
+        let value_type = TypeIndication::infer_from_typed_value(value);
+        if element_type != value_type {
+            return Err(TydiLangError::new(format!("{} searches a {} in an array of {}", function.read().unwrap().get_function_id(), value_type.to_string(), element_type.to_string()), function.read().unwrap().get_code_location()));
+        }
+        check_comparable_value(function.clone(), &array[0])?;
+    }
+    return Ok(array.iter().position(|element| element == value));
+}
+
+struct FunctionLen {}
+
+impl FunctionLen {
+    const ID: &str = "len";
+}
+
+impl FunctionTrait for FunctionLen {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        check_function_arg_count(function.clone(), &function_arg_values, 1)?;
+        let array = get_array_arg(function.clone(), &function_arg_values, 0)?;
+        return Ok(TypedValue::IntValue(array.len() as i128));
+    }
+}
+
+struct FunctionSlice {}
+
+impl FunctionSlice {
+    const ID: &str = "slice";
+}
+
+impl FunctionTrait for FunctionSlice {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    // slice(array, start, end) returns the elements from start to end (exclusive), end is the array length by default
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        if function_arg_values.len() != 2 && function_arg_values.len() != 3 {
+            return Err(TydiLangError::new(format!("slice function has 2 or 3 arguments, get {}", function_arg_values.len()), function.read().unwrap().get_code_location()));
+        }
+        let array = get_array_arg(function.clone(), &function_arg_values, 0)?;
+        let start = get_int_arg(function.clone(), &function_arg_values, 1)?;
+        let end = if function_arg_values.len() == 3 { get_int_arg(function.clone(), &function_arg_values, 2)? } else { array.len() as i128 };
+        if start < 0 || end < start || end > array.len() as i128 {
+            return Err(TydiLangError::new(format!("slice [{}, {}) is out of the array range [0, {})", start, end, array.len()), function.read().unwrap().get_code_location()));
+        }
+        return Ok(TypedValue::Array(array[start as usize .. end as usize].to_vec()));
+    }
+}
+
+struct FunctionReverse {}
+
+impl FunctionReverse {
+    const ID: &str = "reverse";
+}
+
+impl FunctionTrait for FunctionReverse {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        check_function_arg_count(function.clone(), &function_arg_values, 1)?;
+        let mut array = get_array_arg(function.clone(), &function_arg_values, 0)?;
+        array.reverse();
+        return Ok(TypedValue::Array(array));
+    }
+}
+
+struct FunctionContains {}
+
+impl FunctionContains {
+    const ID: &str = "contains";
+}
+
+impl FunctionTrait for FunctionContains {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        let index = find_in_array(function.clone(), &function_arg_values)?;
+        return Ok(TypedValue::BoolValue(index.is_some()));
+    }
+}
+
+struct FunctionIndexOf {}
+
+impl FunctionIndexOf {
+    const ID: &str = "index_of";
+}
+
+impl FunctionTrait for FunctionIndexOf {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    // the index of the first element equal to the value, -1 if not found
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        let index = find_in_array(function.clone(), &function_arg_values)?;
+        match index {
+            Some(index) => return Ok(TypedValue::IntValue(index as i128)),
+            None => return Ok(TypedValue::IntValue(-1)),
+        }
+    }
+}
+
+struct FunctionZip {}
+
+impl FunctionZip {
+    const ID: &str = "zip";
+}
+
+impl FunctionTrait for FunctionZip {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    // zip([1, 2], [3, 4]) == [[1, 3], [2, 4]]
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        check_function_arg_count(function.clone(), &function_arg_values, 2)?;
+        let array0 = get_array_arg(function.clone(), &function_arg_values, 0)?;
+        let array1 = get_array_arg(function.clone(), &function_arg_values, 1)?;
+        if array0.len() != array1.len() {
+            return Err(TydiLangError::new(format!("zip requires arrays of the same length, get {} and {}", array0.len(), array1.len()), function.read().unwrap().get_code_location()));
+        }
+        let output = array0.into_iter().zip(array1.into_iter()).map(|(a, b)| TypedValue::Array(vec![a, b])).collect();
+        return Ok(TypedValue::Array(output));
+    }
+}
+
+struct FunctionFlatten {}
+
+impl FunctionFlatten {
+    const ID: &str = "flatten";
+}
+
+impl FunctionTrait for FunctionFlatten {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    // removes one level of nesting, flatten([[1, 2], [3]]) == [1, 2, 3]
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        check_function_arg_count(function.clone(), &function_arg_values, 1)?;
+        let array = get_array_arg(function.clone(), &function_arg_values, 0)?;
+        let mut output = vec![];
+        for element in array {
+            match element {
+                TypedValue::Array(mut inner) => output.append(&mut inner),
+                _ => return Err(TydiLangError::new(format!("flatten requires every element to be an array, get {}", element.get_brief_info()), function.read().unwrap().get_code_location())),
+            }
+        }
+        return Ok(TypedValue::Array(output));
+    }
+}
+
+struct FunctionSum {}
+
+impl FunctionSum {
+    const ID: &str = "sum";
+}
+
+impl FunctionTrait for FunctionSum {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        check_function_arg_count(function.clone(), &function_arg_values, 1)?;
+        let array = get_array_arg(function.clone(), &function_arg_values, 0)?;
+        check_same_element_type(function.clone(), &array)?;
+        match array.get(0) {
+            None => return Ok(TypedValue::IntValue(0)),
+            Some(TypedValue::IntValue(_)) => {
+                let mut output: i128 = 0;
+                for element in &array {
+                    if let TypedValue::IntValue(v) = element {
+                        output = output.checked_add(*v).ok_or(overflow_error(function.clone()))?;
+                    }
+                }
+                return Ok(TypedValue::IntValue(output));
+            },
+            Some(TypedValue::FloatValue(_)) => {
+                let mut output: f64 = 0.0;
+                for element in &array {
+                    if let TypedValue::FloatValue(v) = element {
+                        output += *v;
+                    }
+                }
+                return Ok(TypedValue::FloatValue(output));
+            },
+            Some(value) => return Err(type_error(function.clone(), 0, "an array of integers or floats", value)),
+        }
+    }
+}
+
+struct FunctionUnique {}
+
+impl FunctionUnique {
+    const ID: &str = "unique";
+}
+
+impl FunctionTrait for FunctionUnique {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    // removes duplicated elements and keeps the first occurrence
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        check_function_arg_count(function.clone(), &function_arg_values, 1)?;
+        let array = get_array_arg(function.clone(), &function_arg_values, 0)?;
+        check_same_element_type(function.clone(), &array)?;
+        let mut output: Vec<TypedValue> = vec![];
+        for element in array {
+            check_comparable_value(function.clone(), &element)?;
+            if !output.contains(&element) {
+                output.push(element);
+            }
+        }
+        return Ok(TypedValue::Array(output));
+    }
+}
+
+fn basic_value_to_string(value: &TypedValue) -> Option<String> {
+    match value {
+        TypedValue::IntValue(v) => return Some(v.to_string()),
+        TypedValue::StringValue(v) => return Some(v.to_string()),
+        TypedValue::BoolValue(v) => return Some(v.to_string()),
+        TypedValue::FloatValue(v) => return Some(v.to_string()),
+        TypedValue::ClockDomainValue(v) => return Some(v.to_string()),
+        _ => return None,
+    }
+}
+
+fn get_string_arg(function: Arc<RwLock<Function>>, function_arg_values: &BTreeMap<usize, TypedValue>, index: usize) -> Result<String, TydiLangError> {
+    let value = function_arg_values.get(&index).expect("bug: arg index out of range");
+    match value {
+        TypedValue::StringValue(v) => return Ok(v.clone()),
+        _ => return Err(type_error(function.clone(), index, "a string", value)),
+    }
+}
+
+fn check_non_empty_string_arg(function: Arc<RwLock<Function>>, value: &String, index: usize) -> Result<(), TydiLangError> {
+    if value.is_empty() {
+        return Err(TydiLangError::new(format!("argument {} of {} cannot be an empty string", index, function.read().unwrap().get_function_id()), function.read().unwrap().get_code_location()));
+    }
+    return Ok(());
+}
+
+struct FunctionFormat {}
+
+impl FunctionFormat {
+    const ID: &str = "format";
+}
+
+impl FunctionTrait for FunctionFormat {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    // format("lane_{}", 2) == "lane_2", "{{" and "}}" are escaped braces
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        if function_arg_values.len() == 0 {
+            return Err(TydiLangError::new(format!("format function requires a format string"), function.read().unwrap().get_code_location()));
+        }
+        let format_string = get_string_arg(function.clone(), &function_arg_values, 0)?;
+        let mut output = String::new();
+        let mut next_arg_index = 1;
+        let mut chars = format_string.chars().peekable();
+        while let Some(c) = chars.next() {
+            match (c, chars.peek()) {
+                ('{', Some('{')) | ('}', Some('}')) => {
+                    chars.next();
+                    output.push(c);
+                },
+                ('{', Some('}')) => {
+                    chars.next();
+                    let arg_value = match function_arg_values.get(&next_arg_index) {
+                        Some(v) => v,
+                        None => return Err(TydiLangError::new(format!("format string \"{}\" has more placeholders than the {} value(s) given", format_string, function_arg_values.len() - 1), function.read().unwrap().get_code_location())),
+                    };
+                    match basic_value_to_string(arg_value) {
+                        Some(v) => output.push_str(&v),
+                        None => return Err(type_error(function.clone(), next_arg_index, "a basic value", arg_value)),
+                    }
+                    next_arg_index += 1;
+                },
+                ('{', _) | ('}', _) => return Err(TydiLangError::new(format!("format string \"{}\" has an unmatched brace, use {{{{ or }}}} to escape it", format_string), function.read().unwrap().get_code_location())),
+                _ => output.push(c),
+            }
+        }
+        if next_arg_index != function_arg_values.len() {
+            return Err(TydiLangError::new(format!("format string \"{}\" has {} placeholder(s), get {} value(s)", format_string, next_arg_index - 1, function_arg_values.len() - 1), function.read().unwrap().get_code_location()));
+        }
+        return Ok(TypedValue::StringValue(output));
+    }
+}
+
+struct FunctionSplit {}
+
+impl FunctionSplit {
+    const ID: &str = "split";
+}
+
+impl FunctionTrait for FunctionSplit {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        check_function_arg_count(function.clone(), &function_arg_values, 2)?;
+        let value = get_string_arg(function.clone(), &function_arg_values, 0)?;
+        let separator = get_string_arg(function.clone(), &function_arg_values, 1)?;
+        check_non_empty_string_arg(function.clone(), &separator, 1)?;
+        let output = value.split(&separator).map(|part| TypedValue::StringValue(part.to_string())).collect();
+        return Ok(TypedValue::Array(output));
+    }
+}
+
+struct FunctionJoin {}
+
+impl FunctionJoin {
+    const ID: &str = "join";
+}
+
+impl FunctionTrait for FunctionJoin {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        check_function_arg_count(function.clone(), &function_arg_values, 2)?;
+        let array = get_array_arg(function.clone(), &function_arg_values, 0)?;
+        let separator = get_string_arg(function.clone(), &function_arg_values, 1)?;
+        let mut parts = vec![];
+        for element in &array {
+            match element {
+                TypedValue::StringValue(v) => parts.push(v.clone()),
+                _ => return Err(TydiLangError::new(format!("join requires an array of strings, get {}", element.get_brief_info()), function.read().unwrap().get_code_location())),
+            }
+        }
+        return Ok(TypedValue::StringValue(parts.join(&separator)));
+    }
+}
+
+struct FunctionReplace {}
+
+impl FunctionReplace {
+    const ID: &str = "replace";
+}
+
+impl FunctionTrait for FunctionReplace {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        check_function_arg_count(function.clone(), &function_arg_values, 3)?;
+        let value = get_string_arg(function.clone(), &function_arg_values, 0)?;
+        let from = get_string_arg(function.clone(), &function_arg_values, 1)?;
+        let to = get_string_arg(function.clone(), &function_arg_values, 2)?;
+        check_non_empty_string_arg(function.clone(), &from, 1)?;
+        return Ok(TypedValue::StringValue(value.replace(&from, &to)));
+    }
+}
+
+struct FunctionUpper {}
+
+impl FunctionUpper {
+    const ID: &str = "upper";
+}
+
+impl FunctionTrait for FunctionUpper {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        check_function_arg_count(function.clone(), &function_arg_values, 1)?;
+        let value = get_string_arg(function.clone(), &function_arg_values, 0)?;
+        return Ok(TypedValue::StringValue(value.to_uppercase()));
+    }
+}
+
+struct FunctionLower {}
+
+impl FunctionLower {
+    const ID: &str = "lower";
+}
+
+impl FunctionTrait for FunctionLower {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        check_function_arg_count(function.clone(), &function_arg_values, 1)?;
+        let value = get_string_arg(function.clone(), &function_arg_values, 0)?;
+        return Ok(TypedValue::StringValue(value.to_lowercase()));
+    }
+}
+
+struct FunctionStartsWith {}
+
+impl FunctionStartsWith {
+    const ID: &str = "starts_with";
+}
+
+impl FunctionTrait for FunctionStartsWith {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        check_function_arg_count(function.clone(), &function_arg_values, 2)?;
+        let value = get_string_arg(function.clone(), &function_arg_values, 0)?;
+        let prefix = get_string_arg(function.clone(), &function_arg_values, 1)?;
+        return Ok(TypedValue::BoolValue(value.starts_with(&prefix)));
+    }
+}
+
+struct FunctionSubstring {}
+
+impl FunctionSubstring {
+    const ID: &str = "substring";
+}
+
+impl FunctionTrait for FunctionSubstring {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    // substring(s, start, end) counts characters, end is the string length by default
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        if function_arg_values.len() != 2 && function_arg_values.len() != 3 {
+            return Err(TydiLangError::new(format!("substring function has 2 or 3 arguments, get {}", function_arg_values.len()), function.read().unwrap().get_code_location()));
+        }
+        let value: Vec<char> = get_string_arg(function.clone(), &function_arg_values, 0)?.chars().collect();
+        let start = get_int_arg(function.clone(), &function_arg_values, 1)?;
+        let end = if function_arg_values.len() == 3 { get_int_arg(function.clone(), &function_arg_values, 2)? } else { value.len() as i128 };
+        if start < 0 || end < start || end > value.len() as i128 {
+            return Err(TydiLangError::new(format!("substring [{}, {}) is out of the string range [0, {})", start, end, value.len()), function.read().unwrap().get_code_location()));
+        }
+        return Ok(TypedValue::StringValue(value[start as usize .. end as usize].iter().collect()));
+    }
+}
+
+// follow the reference of a type variable, e.g. "T" in a template refers to the type variable given as the template argument
+fn resolve_type_value(value: &TypedValue) -> TypedValue {
+    match value {
+        TypedValue::RefToVar(var) => return resolve_logic_type_var(var.clone()).read().unwrap().get_value(),
+        _ => return value.clone(),
+    }
+}
+
+fn get_logic_type_arg(function: Arc<RwLock<Function>>, function_arg_values: &BTreeMap<usize, TypedValue>, index: usize) -> Result<LogicType, TydiLangError> {
+    let value = resolve_type_value(function_arg_values.get(&index).expect("bug: arg index out of range"));
+    match &value {
+        TypedValue::LogicTypeValue(logic_type) => return Ok(logic_type.read().unwrap().clone()),
+        _ => return Err(type_error(function.clone(), index, "a logic type", &value)),
+    }
+}
+
+// the fields of a Group or a Union in the order they are declared
+fn get_logic_type_fields_in_order(function: Arc<RwLock<Function>>, logic_type: &LogicType, index: usize) -> Result<Vec<Arc<RwLock<Variable>>>, TydiLangError> {
+    let scope = match logic_type {
+        LogicType::LogicGroupType(group) => group.read().unwrap().get_scope(),
+        LogicType::LogicUnionType(union) => union.read().unwrap().get_scope(),
+        _ => return Err(TydiLangError::new(format!("argument {} of {} can only be a Group or a Union, get {}", index, function.read().unwrap().get_function_id(), logic_type.get_brief_info()), function.read().unwrap().get_code_location())),
+    };
+    let mut fields: Vec<Arc<RwLock<Variable>>> = scope.read().unwrap().get_variables().into_values().filter(|var| var.read().unwrap().get_is_property_of_scope()).collect();
+    fields.sort_by_key(|var| (var.read().unwrap().get_code_location().begin, var.read().unwrap().get_name()));
+    return Ok(fields);
+}
+
+// the number of bits of a logic type, a Stream inside a Group or a Union is a separate stream and doesn't contribute to the width
+fn get_bit_width(function: Arc<RwLock<Function>>, value: &TypedValue, nested: bool) -> Result<i128, TydiLangError> {
+    let value = resolve_type_value(value);
+    match &value {
+        TypedValue::Array(array) => {
+            let mut output: i128 = 0;
+            for element in array {
+                output = output.checked_add(get_bit_width(function.clone(), element, nested)?).ok_or(overflow_error(function.clone()))?;
+            }
+            return Ok(output);
+        },
+        TypedValue::LogicTypeValue(logic_type) => {
+            let logic_type = logic_type.read().unwrap().clone();
+            match &logic_type {
+                LogicType::LogicNullType => return Ok(0),
+                LogicType::LogicBitType(bit) => {
+                    let bit_width = bit.read().unwrap().get_bit_width().read().unwrap().get_value();
+                    match bit_width {
+                        TypedValue::IntValue(v) => return Ok(v),
+                        _ => return Err(TydiLangError::new(format!("the bit width of {} is not evaluated", logic_type.get_brief_info()), function.read().unwrap().get_code_location())),
+                    }
+                },
+                LogicType::LogicGroupType(_) => {
+                    let mut output: i128 = 0;
+                    for field in get_logic_type_fields_in_order(function.clone(), &logic_type, 0)? {
+                        let field_value = field.read().unwrap().get_value();
+                        output = output.checked_add(get_bit_width(function.clone(), &field_value, true)?).ok_or(overflow_error(function.clone()))?;
+                    }
+                    return Ok(output);
+                },
+                LogicType::LogicUnionType(_) => {
+                    //a union carries a tag to select the field and the data of its widest field
+                    let fields = get_logic_type_fields_in_order(function.clone(), &logic_type, 0)?;
+                    let tag_width = if fields.len() <= 1 { 0 } else { ((fields.len() - 1).ilog2() + 1) as i128 };
+                    let mut data_width: i128 = 0;
+                    for field in fields {
+                        let field_value = field.read().unwrap().get_value();
+                        data_width = data_width.max(get_bit_width(function.clone(), &field_value, true)?);
+                    }
+                    return Ok(tag_width + data_width);
+                },
+                LogicType::LogicStreamType(_) => {
+                    if nested {
+                        return Ok(0);
+                    }
+                    return Err(TydiLangError::new(format!("bit_width cannot be applied to a Stream, apply it to the element type of the stream instead"), function.read().unwrap().get_code_location()));
+                },
+            }
+        },
+        _ => return Err(type_error(function.clone(), 0, "a logic type", &value)),
+    }
+}
+
+struct FunctionBitWidth {}
+
+impl FunctionBitWidth {
+    const ID: &str = "bit_width";
+}
+
+impl FunctionTrait for FunctionBitWidth {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        check_function_arg_count(function.clone(), &function_arg_values, 1)?;
+        let value = function_arg_values.get(&0).expect("bug: arg not on index 0");
+        let bit_width = get_bit_width(function.clone(), value, false)?;
+        return Ok(TypedValue::IntValue(bit_width));
+    }
+}
+
+struct FunctionFieldNames {}
+
+impl FunctionFieldNames {
+    const ID: &str = "field_names";
+}
+
+impl FunctionTrait for FunctionFieldNames {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        check_function_arg_count(function.clone(), &function_arg_values, 1)?;
+        let logic_type = get_logic_type_arg(function.clone(), &function_arg_values, 0)?;
+        let fields = get_logic_type_fields_in_order(function.clone(), &logic_type, 0)?;
+        let output = fields.iter().map(|field| TypedValue::StringValue(field.read().unwrap().get_name())).collect();
+        return Ok(TypedValue::Array(output));
+    }
+}
+
+struct FunctionFieldType {}
+
+impl FunctionFieldType {
+    const ID: &str = "field_type";
+}
+
+impl FunctionTrait for FunctionFieldType {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        check_function_arg_count(function.clone(), &function_arg_values, 2)?;
+        let logic_type = get_logic_type_arg(function.clone(), &function_arg_values, 0)?;
+        let field_name = get_string_arg(function.clone(), &function_arg_values, 1)?;
+        let fields = get_logic_type_fields_in_order(function.clone(), &logic_type, 0)?;
+        for field in &fields {
+            if field.read().unwrap().get_name() == field_name {
+                return Ok(field.read().unwrap().get_value());
+            }
+        }
+        let field_names: Vec<String> = fields.iter().map(|field| field.read().unwrap().get_name()).collect();
+        return Err(TydiLangError::new(format!("{} doesn't have field {}, available: {:?}", logic_type.get_brief_info(), field_name, field_names), function.read().unwrap().get_code_location()));
+    }
+}
+
+struct FunctionIsStream {}
+
+impl FunctionIsStream {
+    const ID: &str = "is_stream";
+}
+
+impl FunctionTrait for FunctionIsStream {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        check_function_arg_count(function.clone(), &function_arg_values, 1)?;
+        let logic_type = get_logic_type_arg(function.clone(), &function_arg_values, 0)?;
+        return Ok(TypedValue::BoolValue(matches!(logic_type, LogicType::LogicStreamType(_))));
+    }
+}
+
+struct FunctionIsGroup {}
+
+impl FunctionIsGroup {
+    const ID: &str = "is_group";
+}
+
+impl FunctionTrait for FunctionIsGroup {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        check_function_arg_count(function.clone(), &function_arg_values, 1)?;
+        let logic_type = get_logic_type_arg(function.clone(), &function_arg_values, 0)?;
+        return Ok(TypedValue::BoolValue(matches!(logic_type, LogicType::LogicGroupType(_))));
+    }
+}
+
+struct FunctionElementCount {}
+
+impl FunctionElementCount {
+    const ID: &str = "element_count";
+}
+
+impl FunctionTrait for FunctionElementCount {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        check_function_arg_count(function.clone(), &function_arg_values, 1)?;
+        let logic_type = get_logic_type_arg(function.clone(), &function_arg_values, 0)?;
+        let fields = get_logic_type_fields_in_order(function.clone(), &logic_type, 0)?;
+        return Ok(TypedValue::IntValue(fields.len() as i128));
+    }
+}
+
+// parse an integer with the same syntax as an integer literal, e.g. "-8", "0x1F" and "1_000"
+fn parse_int_string(function: Arc<RwLock<Function>>, value: &String) -> Result<i128, TydiLangError> {
+    let parse_error = TydiLangError::new(format!("{} cannot parse \"{}\" as an integer", function.read().unwrap().get_function_id(), value), function.read().unwrap().get_code_location());
+    let trimmed = value.trim();
+    let (is_negative, digits) = match trimmed.strip_prefix("-") {
+        Some(digits) => (true, digits),
+        None => (false, trimmed),
+    };
+    let int_pair = match TydiLangSrc::parse(Rule::INT, digits) {
+        Ok(mut pairs) => pairs.next().expect("bug: INT rule without a pair"),
+        Err(_) => return Err(parse_error),
+    };
+    if int_pair.as_str() != digits {
+        return Err(parse_error);
+    }
+    let output = evaluate_IntExp(int_pair).map_err(|_| overflow_error(function.clone()))?;
+    if is_negative {
+        return output.checked_neg().ok_or(overflow_error(function.clone()));
+    }
+    return Ok(output);
+}
+
+struct FunctionInt {}
+
+impl FunctionInt {
+    const ID: &str = "int";
+}
+
+impl FunctionTrait for FunctionInt {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    // int(x, mode) converts a float with the rounding mode: "trunc" (default), "floor", "ceil" or "round"
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        if function_arg_values.len() != 1 && function_arg_values.len() != 2 {
+            return Err(TydiLangError::new(format!("int function has 1 or 2 arguments, get {}", function_arg_values.len()), function.read().unwrap().get_code_location()));
+        }
+        let rounding: fn(f64) -> f64 = if function_arg_values.len() == 2 {
+            match get_string_arg(function.clone(), &function_arg_values, 1)?.as_str() {
+                "trunc" => f64::trunc,
+                "floor" => f64::floor,
+                "ceil" => f64::ceil,
+                "round" => f64::round,
+                mode => return Err(TydiLangError::new(format!("unknown rounding mode \"{}\" of int, available: trunc(default)/floor/ceil/round", mode), function.read().unwrap().get_code_location())),
+            }
+        } else { f64::trunc };
+        let value = function_arg_values.get(&0).expect("bug: arg not on index 0");
+        match value {
+            TypedValue::IntValue(v) => return Ok(TypedValue::IntValue(*v)),
+            TypedValue::FloatValue(v) => return float_to_int(function.clone(), rounding(*v)),
+            TypedValue::BoolValue(v) => return Ok(TypedValue::IntValue(*v as i128)),
+            TypedValue::StringValue(v) => return Ok(TypedValue::IntValue(parse_int_string(function.clone(), v)?)),
+            _ => return Err(type_error(function.clone(), 0, "an integer, a float, a bool or a string", value)),
+        }
+    }
+}
+
+struct FunctionFloat {}
+
+impl FunctionFloat {
+    const ID: &str = "float";
+}
+
+impl FunctionTrait for FunctionFloat {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        check_function_arg_count(function.clone(), &function_arg_values, 1)?;
+        let value = function_arg_values.get(&0).expect("bug: arg not on index 0");
+        match value {
+            TypedValue::IntValue(v) => return Ok(TypedValue::FloatValue(*v as f64)),
+            TypedValue::FloatValue(v) => return Ok(TypedValue::FloatValue(*v)),
+            TypedValue::StringValue(v) => {
+                match v.trim().parse::<f64>() {
+                    Ok(output) if output.is_finite() => return Ok(TypedValue::FloatValue(output)),
+                    _ => return Err(TydiLangError::new(format!("float cannot parse \"{}\" as a float", v), function.read().unwrap().get_code_location())),
+                }
+            },
+            _ => return Err(type_error(function.clone(), 0, "an integer, a float or a string", value)),
+        }
+    }
+}
+
+struct FunctionBool {}
+
+impl FunctionBool {
+    const ID: &str = "bool";
+}
+
+impl FunctionTrait for FunctionBool {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+        check_function_arg_count(function.clone(), &function_arg_values, 1)?;
+        let value = function_arg_values.get(&0).expect("bug: arg not on index 0");
+        match value {
+            TypedValue::BoolValue(v) => return Ok(TypedValue::BoolValue(*v)),
+            TypedValue::StringValue(v) => {
+                match v.trim() {
+                    "true" => return Ok(TypedValue::BoolValue(true)),
+                    "false" => return Ok(TypedValue::BoolValue(false)),
+                    _ => return Err(TydiLangError::new(format!("bool cannot parse \"{}\" as a bool, only \"true\" and \"false\" are accepted", v), function.read().unwrap().get_code_location())),
+                }
+            },
+            _ => return Err(type_error(function.clone(), 0, "a bool or a string", value)),
+        }
+    }
+}
+
+// record a diagnostic message, the values are separated by spaces, e.g. print("width:", n)
+fn report_diagnostic(function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>, level: DiagnosticLevel) -> Result<TypedValue, TydiLangError> {
+    let function_arg_values = evaluate_function_args(function.clone(), scope.clone(), evaluator.clone())?;
+    if level != DiagnosticLevel::Print && function_arg_values.len() == 0 {
+        return Err(TydiLangError::new(format!("{} function requires a message", function.read().unwrap().get_function_id()), function.read().unwrap().get_code_location()));
+    }
+    let mut messages = vec![];
+    for (_, value) in &function_arg_values {
+        match basic_value_to_string(value) {
+            Some(message) => messages.push(message),
+            None => messages.push(value.get_brief_info()),
+        }
+    }
+    let diagnostic = Diagnostic::new(level, messages.join(" "), function.read().unwrap().get_code_location());
+    evaluator.write().unwrap().add_diagnostic(diagnostic);
+    return Ok(TypedValue::Null);
+}
+
+struct FunctionPrint {}
+
+impl FunctionPrint {
+    const ID: &str = "print";
+}
+
+impl FunctionTrait for FunctionPrint {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    // prints a message after evaluation, useful to debug template instantiations
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        return report_diagnostic(function, scope, evaluator, DiagnosticLevel::Print);
+    }
+}
+
+struct FunctionWarn {}
+
+impl FunctionWarn {
+    const ID: &str = "warn";
+}
+
+impl FunctionTrait for FunctionWarn {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    // reports a warning but the evaluation continues, e.g. for deprecated templates
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        return report_diagnostic(function, scope, evaluator, DiagnosticLevel::Warning);
+    }
+}
+
+struct FunctionError {}
+
+impl FunctionError {
+    const ID: &str = "error";
+}
+
+impl FunctionTrait for FunctionError {
+    fn get_id() -> String where Self: Sized {
+        return String::from(Self::ID);
+    }
+
+    // unlike assert, the evaluation continues and fails at the end, so all errors are reported
+    fn execute(&self, function: Arc<RwLock<Function>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+        return report_diagnostic(function, scope, evaluator, DiagnosticLevel::Error);
+    }
+}
@@ -5,11 +5,101 @@ use crate::deep_clone::DeepClone;
 use crate::error::TydiLangError;
 use crate::generate_name::{generate_init_value, generate_template_instance_name, generate_template_instance_name_based_on_old_name};
 use crate::trait_common::GetName;
-use crate::tydi_memory_representation::{Variable, TypedValue, Scope, TraitCodeLocationAccess, LogicType, GetScope, EvaluationStatus, ScopeRelationType, ImplementationType, GlobalIdentifier};
-use crate::evaluation::{evaluate_id_in_typed_value, evaluate_var, Evaluator};
+use crate::tydi_memory_representation::{Variable, TypedValue, Scope, TraitCodeLocationAccess, LogicType, GetScope, EvaluationStatus, ScopeRelationType, ImplementationType, GlobalIdentifier, TypeIndication, TemplateArg, CodeLocation};
+use crate::evaluation::{evaluate_id_in_typed_value, evaluate_var, evaluate_expression, Evaluator};
 
+// a string is accepted as the name of a clock domain, e.g. "clk_a" for "clk: clock", also for each element of "clks: [clock]"
+pub fn convert_template_arg_value(template_arg_type: &TypeIndication, value: TypedValue) -> TypedValue {
+    return match (template_arg_type, &value) {
+        (TypeIndication::Clockdomain, TypedValue::StringValue(v)) => TypedValue::ClockDomainValue(v.clone()),
+        (TypeIndication::Clockdomain, TypedValue::Array(elements)) => TypedValue::Array(elements.iter().map(|element| convert_template_arg_value(template_arg_type, element.clone())).collect()),
+        _ => value,
+    };
+}
+
+// an array argument, e.g. <widths: [int]>, requires every element to match the type
+pub fn is_template_arg_compatible(template_arg_is_array: bool, template_arg_type: &TypeIndication, value: &TypedValue) -> bool {
+    return match template_arg_is_array {
+        true => TypeIndication::Array(Box::new(template_arg_type.clone())).is_compatible_with_typed_value(value),
+        false => template_arg_type.is_compatible_with_typed_value(value),
+    };
+}
 
-pub fn try_template_expansion(template_var: Arc<RwLock<Variable>>, template_exps: &Option<BTreeMap<usize, TypedValue>>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<Arc<RwLock<Variable>>, TydiLangError> {
+fn get_logic_type_kind(logic_type: &LogicType) -> String {
+    return match logic_type {
+        LogicType::LogicNullType => format!("Null"),
+        LogicType::LogicBitType(_) => format!("Bit"),
+        LogicType::LogicGroupType(_) => format!("Group"),
+        LogicType::LogicUnionType(_) => format!("Union"),
+        LogicType::LogicStreamType(_) => format!("Stream"),
+    };
+}
+
+// constraints in the template header, e.g. <N: int where N > 0> and <T: type is Stream>, are checked after the argument is added to the instance scope
+// a failure reports both the instantiation site and the declaration of the argument
+fn check_template_arg_constraints(template_var: Arc<RwLock<Variable>>, arg: &TemplateArg, value: &TypedValue, location: &CodeLocation, new_instance_scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<(), TydiLangError> {
+    let locations = vec![location.clone(), arg.get_code_location()];
+    if let Some(kind) = arg.get_kind_constraint() {
+        let value_kind = match value {
+            TypedValue::LogicTypeValue(logic_type) => get_logic_type_kind(&logic_type.read().unwrap()),
+            _ => unreachable!("bug: the type of template argument {} is checked before its constraints", arg.get_name()),
+        };
+        if value_kind != kind {
+            return Err(TydiLangError::new_multiple_locations(format!("template argument {} of variable {} must be a {}, get {}", arg.get_name(), template_var.read().unwrap().get_name(), kind, value.get_brief_info()), locations));
+        }
+    }
+    if let Some(constraint_exp) = arg.get_constraint_exp() {
+        let constraint_value = evaluate_expression(constraint_exp.clone(), None, new_instance_scope.clone(), evaluator.clone())?;
+        let constraint_value = evaluate_id_in_typed_value(constraint_value, None, ScopeRelationType::resolve_id_default(), None, new_instance_scope.clone(), evaluator.clone())?;
+        match constraint_value {
+            TypedValue::BoolValue(true) => (),
+            TypedValue::BoolValue(false) => return Err(TydiLangError::new_multiple_locations(format!("template argument {} of variable {} doesn't satisfy the constraint ({}), get {}", arg.get_name(), template_var.read().unwrap().get_name(), constraint_exp, value.get_brief_info()), locations)),
+            _ => return Err(TydiLangError::new_multiple_locations(format!("the constraint ({}) of template argument {} must be a bool, get {}", constraint_exp, arg.get_name(), constraint_value.get_brief_info()), locations)),
+        }
+    }
+    if let Some(streamlet_exp) = arg.get_impl_of_exp() {
+        let expected_value = evaluate_expression(streamlet_exp.clone(), None, new_instance_scope.clone(), evaluator.clone())?;
+        let expected_value = evaluate_id_in_typed_value(expected_value, None, ScopeRelationType::resolve_id_default(), None, new_instance_scope.clone(), evaluator.clone())?;
+        let expected_streamlet = match &expected_value {
+            TypedValue::Streamlet(streamlet) => streamlet.clone(),
+            _ => return Err(TydiLangError::new_multiple_locations(format!("{} is not a streamlet, but template argument {} is an implementation of it", expected_value.get_brief_info(), arg.get_name()), locations)),
+        };
+        let implementation = match value {
+            TypedValue::Implementation(implementation) => implementation.clone(),
+            _ => unreachable!("bug: the type of template argument {} is checked before its constraints", arg.get_name()),
+        };
+        let derived_streamlet_var = implementation.read().unwrap().get_derived_streamlet_var();
+        let impl_scope = implementation.read().unwrap().get_scope();
+        let derived_streamlet_value = evaluate_var(derived_streamlet_var.clone(), impl_scope.clone(), evaluator.clone())?;
+        let derived_streamlet = match &derived_streamlet_value {
+            TypedValue::Streamlet(streamlet) => streamlet.clone(),
+            _ => return Err(TydiLangError::new(format!("{} is not a streamlet, but used in defining impl({})", derived_streamlet_var.read().unwrap().get_name(), implementation.read().unwrap().get_name()), derived_streamlet_var.read().unwrap().get_code_location())),
+        };
+        //an implementation of a streamlet extending the expected streamlet is also accepted
+        let mut current_streamlet = Some(derived_streamlet);
+        let mut is_derived_from_expected = false;
+        while let Some(streamlet) = current_streamlet {
+            if Arc::ptr_eq(&streamlet, &expected_streamlet) {
+                is_derived_from_expected = true;
+                break;
+            }
+            current_streamlet = streamlet.read().unwrap().get_extends_streamlet();
+        }
+        if !is_derived_from_expected {
+            return Err(TydiLangError::new_multiple_locations(format!("template argument {} of variable {} must be an implementation of {} or of a streamlet extending it, get {}", arg.get_name(), template_var.read().unwrap().get_name(), expected_streamlet.read().unwrap().get_name(), value.get_brief_info()), locations));
+        }
+    }
+    return Ok(());
+}
+
+pub fn template_arg_type_to_string(template_arg_is_array: bool, template_arg_type: &TypeIndication) -> String {
+    if template_arg_is_array {
+        return format!("[{}]", template_arg_type.to_string());
+    }
+    return template_arg_type.to_string();
+}
+
+pub fn try_template_expansion(template_var: Arc<RwLock<Variable>>, template_exps: &Option<BTreeMap<usize, TypedValue>>, named_template_exps: &BTreeMap<String, TypedValue>, location: &CodeLocation, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<Arc<RwLock<Variable>>, TydiLangError> {
     let template_var_type = template_var.read().unwrap().get_value();
 
     let template_args;
@@ -61,73 +151,87 @@ pub fn try_template_expansion(template_var: Arc<RwLock<Variable>>, template_exps
     }
 
     //check template exps match args
-    {
-        if template_args.is_none() && template_exps.is_none() {
-            return Ok(template_var);        //this is not a template and no template args are provided
-        }
-        if template_args.is_none() && !template_exps.is_none() {
-            return Err(TydiLangError::new(format!("variable {} is not a template, but no template expression are given", template_var.read().unwrap().get_name()), template_var.read().unwrap().get_code_location()));
-        }
-        if !template_args.is_none() && template_exps.is_none() {
-            return Err(TydiLangError::new(format!("variable {} is a template, but no template expression are given", template_var.read().unwrap().get_name()), template_var.read().unwrap().get_code_location()));
-        }
-        let template_args = template_args.as_ref().unwrap();
-        let template_exps = template_exps.as_ref().unwrap();
-        if template_args.len() != template_exps.len() {
-            return Err(TydiLangError::new(format!("variable {} has {} template args, but provide {} expression(s)", template_var.read().unwrap().get_name(), template_args.len(), template_exps.len()), template_var.read().unwrap().get_code_location()));
+    if template_args.is_none() && template_exps.is_none() && named_template_exps.is_empty() {
+        return Ok(template_var);        //this is not a template and no template args are provided
+    }
+    if template_args.is_none() {
+        return Err(TydiLangError::new(format!("variable {} is not a template, but no template expression are given", template_var.read().unwrap().get_name()), template_var.read().unwrap().get_code_location()));
+    }
+    let template_args = template_args.as_ref().unwrap();
+    let positional_template_exps = template_exps.clone().unwrap_or(BTreeMap::new());
+    if positional_template_exps.len() > template_args.len() {
+        return Err(TydiLangError::new(format!("variable {} has {} template args, but provide {} expression(s)", template_var.read().unwrap().get_name(), template_args.len(), positional_template_exps.len()), template_var.read().unwrap().get_code_location()));
+    }
+    for arg_name in named_template_exps.keys() {
+        let arg_index = template_args.iter().find(|(_, arg)| &arg.get_name() == arg_name).map(|(index, _)| *index);
+        match arg_index {
+            None => return Err(TydiLangError::new(format!("variable {} has no template argument named {}", template_var.read().unwrap().get_name(), arg_name), template_var.read().unwrap().get_code_location())),
+            Some(index) if index < positional_template_exps.len() => return Err(TydiLangError::new(format!("template argument {} of variable {} is given both by position and by name", arg_name, template_var.read().unwrap().get_name()), template_var.read().unwrap().get_code_location())),
+            Some(_) => (),
         }
-        for i in 0..template_args.len() {
-            let template_arg_type = template_args.get(&i).expect("bug: template arg index not from 0 to n").get_type_indication();
-            let template_arg_exp = template_exps.get(&i).expect("bug: template exp index not from 0 to n").clone();
-            let template_arg_exp = evaluate_id_in_typed_value(template_arg_exp, None, ScopeRelationType::resolve_id_default(), None, scope.clone(), evaluator.clone())?;
-            let real_value = match template_arg_exp.try_get_referenced_variable() {
-                Some(real_var) => {
-                    evaluate_var(real_var.clone(), scope.clone(), evaluator.clone())?
-                },
-                None => template_arg_exp,
-            };
+    }
 
-            if !template_arg_type.is_compatible_with_typed_value(&real_value) {
-                return Err(TydiLangError::new(format!("var: {}, template argument index {}, expected {}, get {}", template_var.read().unwrap().get_name(), i, template_arg_type.to_string(), real_value.get_brief_info()), template_var.read().unwrap().get_code_location()));
-            }
-        }
+    //normalize template exps to one value per arg and add template_args to the cloned_scope
+    //an arg is given by position, by name, or falls back to its default value, which is evaluated in the instance scope so that it can refer to earlier args
+    let mut normalized_template_exps = BTreeMap::new();
+    let mut template_arg_values = BTreeMap::new();
+    for i in 0..template_args.len() {
+        let arg = template_args.get(&i).expect("bug: template arg index not from 0 to n");
+        let template_arg_type = arg.get_type_indication();
+        let (template_arg_exp, exp_scope, is_default_value) = match (positional_template_exps.get(&i), named_template_exps.get(&arg.get_name()), arg.get_default_exp()) {
+            (Some(exp), _, _) => (exp.clone(), scope.clone(), false),
+            (None, Some(exp), _) => (exp.clone(), scope.clone(), false),
+            (None, None, Some(default_exp)) => (evaluate_expression(default_exp, None, new_instance_scope.clone(), evaluator.clone())?, new_instance_scope.clone(), true),
+            (None, None, None) => {
+                if positional_template_exps.is_empty() && named_template_exps.is_empty() {
+                    return Err(TydiLangError::new(format!("variable {} is a template, but no template expression are given", template_var.read().unwrap().get_name()), template_var.read().unwrap().get_code_location()));
+                }
+                return Err(TydiLangError::new(format!("template argument {} of variable {} is not given and has no default value", arg.get_name(), template_var.read().unwrap().get_name()), template_var.read().unwrap().get_code_location()));
+            },
+        };
+        let evaluated_exp = evaluate_id_in_typed_value(template_arg_exp.clone(), None, ScopeRelationType::resolve_id_default(), None, exp_scope.clone(), evaluator.clone())?;
+        let real_value = match evaluated_exp.try_get_referenced_variable() {
+            Some(real_var) => {
+                evaluate_var(real_var.clone(), exp_scope.clone(), evaluator.clone())?
+            },
+            None => evaluated_exp,
+        };
+        let real_value = convert_template_arg_value(&template_arg_type, real_value);
 
-        //set new_instance_var
-        {
-            let mut new_instance_var_write = new_instance_var.write().unwrap();
-            new_instance_var_write.set_name(generate_template_instance_name(template_var.clone(), template_exps));
-            new_instance_var_write.set_code_location(template_var.read().unwrap().get_code_location());
+        if !is_template_arg_compatible(arg.get_is_array(), &template_arg_type, &real_value) {
+            return Err(TydiLangError::new(format!("var: {}, template argument index {}, expected {}, get {}", template_var.read().unwrap().get_name(), i, template_arg_type_to_string(arg.get_is_array(), &template_arg_type), real_value.get_brief_info()), template_var.read().unwrap().get_code_location()));
         }
 
-        //set new instance scope
+        //a default value is named by its result, since its expression belongs to the template rather than the caller
+        match is_default_value {
+            true => normalized_template_exps.insert(i, real_value.clone()),
+            false => normalized_template_exps.insert(i, template_arg_exp),
+        };
+
+        template_arg_values.insert(i, real_value.clone());
+
+        let temp_var = Variable::new_builtin(arg.get_name(), real_value.clone());
         {
-            let mut new_instance_scope_write = new_instance_scope.write().unwrap();
-            let old_name = new_instance_scope_write.get_name();
-            new_instance_scope_write.set_name(generate_template_instance_name_based_on_old_name(old_name, template_exps));
+            let mut temp_var_write = temp_var.write().unwrap();
+            temp_var_write.set_evaluated(EvaluationStatus::Evaluated);
         }
+        new_instance_scope.write().unwrap().add_var(temp_var)?;
+        check_template_arg_constraints(template_var.clone(), arg, &real_value, location, new_instance_scope.clone(), evaluator.clone())?;
     }
+    let template_exps = Some(normalized_template_exps);
 
-    //add template_args to the cloned_scope
+    //set new_instance_var
     {
-        let template_args = template_args.as_ref().unwrap();
-        let template_exps = template_exps.as_ref().unwrap();
-        for i in 0..template_args.len() {
-            let exp = template_exps.get(&i).unwrap().clone();
-            let arg = template_args.get(&i).unwrap();
-            let exp = evaluate_id_in_typed_value(exp, None, ScopeRelationType::resolve_id_default(), None, scope.clone(), evaluator.clone())?;
-            let real_value = match exp.try_get_referenced_variable() {
-                Some(real_var) => {
-                    evaluate_var(real_var.clone(), scope.clone(), evaluator.clone())?
-                },
-                None => exp,
-            };
-            let temp_var = Variable::new_builtin(arg.get_name(), real_value);
-            {
-                let mut temp_var_write = temp_var.write().unwrap();
-                temp_var_write.set_evaluated(EvaluationStatus::Evaluated);
-            }
-            new_instance_scope.write().unwrap().add_var(temp_var)?;
-        }
+        let mut new_instance_var_write = new_instance_var.write().unwrap();
+        new_instance_var_write.set_name(generate_template_instance_name(template_var.clone(), &template_arg_values, scope.clone()));
+        new_instance_var_write.set_code_location(template_var.read().unwrap().get_code_location());
+    }
+
+    //set new instance scope
+    {
+        let mut new_instance_scope_write = new_instance_scope.write().unwrap();
+        let old_name = new_instance_scope_write.get_name();
+        new_instance_scope_write.set_name(generate_template_instance_name_based_on_old_name(old_name, &template_arg_values));
     }
 
     //change cloned instance property
@@ -141,8 +245,8 @@ pub fn try_template_expansion(template_var: Arc<RwLock<Variable>>, template_exps
                 () //nothing to do
             },
             TypedValue::Implementation(implementation) => {
-                //set instance of template implementation 
-                implementation.write().unwrap().set_impl_type(ImplementationType::TemplateInstance(implementation.clone(), template_exps.clone().unwrap()));
+                //set instance of template implementation, the arguments are the evaluated values, an expression such as "T" means nothing outside the template
+                implementation.write().unwrap().set_impl_type(ImplementationType::TemplateInstance(implementation.clone(), template_arg_values.clone()));
             },
             _ => unreachable!(),
         }
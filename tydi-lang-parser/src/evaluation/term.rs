@@ -5,9 +5,11 @@ use crate::generate_name::generate_init_value;
 use crate::tydi_parser::*;
 use crate::error::TydiLangError;
 
-use crate::tydi_memory_representation::{Scope, TypedValue, CodeLocation, Identifier, IdentifierType, ScopeRelationType};
+use crate::tydi_memory_representation::{Scope, TypedValue, CodeLocation, Identifier, IdentifierType, ScopeRelationType, ScopeType, SrcInfo, Variable};
 
-use super::{Evaluator, evaluate_expression_pest, evaluate_id_in_typed_value, UnaryOperator};
+use super::{Evaluator, evaluate_expression, evaluate_expression_pest, evaluate_id_in_typed_value, UnaryOperator};
+
+pub const MAX_ARRAY_RANGE_SIZE: usize = 1 << 20;
 
 #[allow(non_snake_case)]
 pub fn evaluate_Term(term: Pair<Rule>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
@@ -20,10 +22,22 @@ pub fn evaluate_Term(term: Pair<Rule>, scope: Arc<RwLock<Scope>>, evaluator: Arc
                 let exp_typed_value = exp.evaluate_TypedValue(scope.clone(), evaluator.clone())?;
                 return Ok(exp_typed_value);
             }
+            Rule::ArrayComprehension => {
+                let value = evaluate_ArrayComprehension(element, scope.clone(), evaluator.clone())?;
+                return Ok(value);
+            }
+            Rule::ArrayRange => {
+                let value = evaluate_ArrayRange(element, scope.clone(), evaluator.clone())?;
+                return Ok(value);
+            }
             Rule::ArrayExp => {
                 let value = evaluate_ArrayExp(element, scope.clone(), evaluator.clone())?;
                 return Ok(value);
             }
+            Rule::MapExp => {
+                let value = evaluate_MapExp(element, scope.clone(), evaluator.clone())?;
+                return Ok(value);
+            }
             Rule::IntExp => {
                 let value = evaluate_Int(element)?;
                 return Ok(TypedValue::IntValue(value));
@@ -174,6 +188,152 @@ pub fn evaluate_ArrayExp(exps: Pair<Rule>, scope: Arc<RwLock<Scope>>, evaluator:
     return Ok(TypedValue::Array(output));
 }
 
+#[allow(non_snake_case)]
+pub fn evaluate_MapExp(exps: Pair<Rule>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+    let mut output = BTreeMap::new();
+    for entry in exps.clone().into_inner().into_iter() {
+        let mut key = String::new();
+        for element in entry.into_inner().into_iter() {
+            let rule = element.as_rule();
+            match rule {
+                Rule::ID => {
+                    key = element.as_str().to_string();
+                }
+                Rule::StringExp => {
+                    key = evaluate_StringExp(element.as_str().to_string())?;
+                }
+                Rule::Exp => {
+                    let value_exp = evaluate_expression_pest(element, None, scope.clone(), evaluator.clone())?;
+                    let value = value_exp.evaluate_TypedValue(scope.clone(), evaluator.clone())?;
+                    let value = evaluate_id_in_typed_value(value, None, ScopeRelationType::resolve_id_default(), None, scope.clone(), evaluator.clone())?;
+                    if output.contains_key(&key) {
+                        return Err(TydiLangError::new(format!("duplicated key \"{}\" in map {}", key, exps.as_str()), CodeLocation::new_unknown()));
+                    }
+                    output.insert(key.clone(), value);
+                }
+                _ => unreachable!()
+            }
+        }
+    }
+    return Ok(TypedValue::Map(output));
+}
+
+#[allow(non_snake_case)]
+pub fn evaluate_ArrayRange(exps: Pair<Rule>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+    //the range is parsed again from the expression, so its location points into the expression
+    let range_location = CodeLocation::new_from_pest_rule(&exps, SrcInfo::new(generate_init_value(), exps.get_input().to_string()));
+    let mut bounds = vec![];
+    let mut is_inclusive = false;
+    for element in exps.clone().into_inner().into_iter() {
+        let rule = element.as_rule();
+        match rule {
+            Rule::Exp => {
+                let bound_exp = element.as_str().to_string();
+                let bound_exp_value = evaluate_expression_pest(element, None, scope.clone(), evaluator.clone())?;
+                let bound_typed_value = bound_exp_value.evaluate_TypedValue(scope.clone(), evaluator.clone())?;
+                let bound_typed_value = evaluate_id_in_typed_value(bound_typed_value, None, ScopeRelationType::resolve_id_default(), None, scope.clone(), evaluator.clone())?;
+                let bound_name = match bounds.len() {
+                    0 => "start",
+                    1 => "end",
+                    _ => "step",
+                };
+                match bound_typed_value {
+                    TypedValue::IntValue(i) => bounds.push(i),
+                    _ => return Err(TydiLangError::new(format!("the {} of range {} can only be an integer, get {{{}}} = {}", bound_name, exps.as_str(), bound_exp, bound_typed_value.get_brief_info()), range_location.clone())),
+                }
+            }
+            Rule::OP_RangeInclusive => {
+                is_inclusive = true;
+            }
+            Rule::OP_RangeExclusive => {
+                is_inclusive = false;
+            }
+            _ => unreachable!()
+        }
+    }
+
+    let start = bounds[0];
+    let end = bounds[1];
+    let step = if bounds.len() == 3 { bounds[2] } else { 1 };
+    if step == 0 {
+        return Err(TydiLangError::new(format!("the step of range {} cannot be 0", exps.as_str()), range_location));
+    }
+
+    let mut output = vec![];
+    let mut i = start;
+    //a negative step counts down from start to end
+    while (step > 0 && (i < end || (is_inclusive && i == end))) || (step < 0 && (i > end || (is_inclusive && i == end))) {
+        if output.len() >= MAX_ARRAY_RANGE_SIZE {
+            return Err(TydiLangError::new(format!("range {} has more than {} elements", exps.as_str(), MAX_ARRAY_RANGE_SIZE), range_location));
+        }
+        output.push(TypedValue::IntValue(i));
+        i = match i.checked_add(step) {
+            Some(next) => next,
+            None => return Err(TydiLangError::new(format!("range {} overflows after {}", exps.as_str(), i), range_location)),
+        };
+    }
+    return Ok(TypedValue::Array(output));
+}
+
+#[allow(non_snake_case)]
+pub fn evaluate_ArrayComprehension(exps: Pair<Rule>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
+    let mut element_exp = String::new();
+    let mut element_var_name = String::new();
+    let mut array_value = TypedValue::UnknwonValue;
+    let mut filter_exp: Option<String> = None;
+    let mut exp_count = 0;
+    for element in exps.clone().into_inner().into_iter() {
+        let rule = element.as_rule();
+        match rule {
+            Rule::Exp => {
+                match exp_count {
+                    0 => element_exp = element.as_str().to_string(),
+                    1 => {
+                        let array_exp = evaluate_expression_pest(element, None, scope.clone(), evaluator.clone())?;
+                        let array_typed_value = array_exp.evaluate_TypedValue(scope.clone(), evaluator.clone())?;
+                        array_value = evaluate_id_in_typed_value(array_typed_value, None, ScopeRelationType::resolve_id_default(), None, scope.clone(), evaluator.clone())?;
+                    },
+                    _ => filter_exp = Some(element.as_str().to_string()),
+                }
+                exp_count += 1;
+            }
+            Rule::ID => {
+                element_var_name = element.as_str().to_string();
+            }
+            Rule::ComprehensionFor | Rule::ComprehensionIn | Rule::ConditionalIf => (),
+            _ => unreachable!()
+        }
+    }
+
+    let array = match array_value {
+        TypedValue::Array(array) => array,
+        TypedValue::Map(map) => map.into_keys().map(|key| TypedValue::StringValue(key)).collect(),
+        _ => return Err(TydiLangError::new(format!("the range of array comprehension {} can only be an array or a map, get {}", exps.as_str(), array_value.get_brief_info()), CodeLocation::new_unknown())),
+    };
+
+    //the element and the filter are evaluated once per element, in a scope where the element variable is defined
+    let mut output = vec![];
+    for (index, single_element) in array.into_iter().enumerate() {
+        let element_scope = Scope::new(format!("comprehension_{}_{}", element_var_name, index), ScopeType::IfForScope, scope.clone());
+        element_scope.write().unwrap().add_var(Variable::new_predefined(element_var_name.clone(), single_element))?;
+
+        if filter_exp.is_some() {
+            let filter_value = evaluate_expression(filter_exp.clone().unwrap(), None, element_scope.clone(), evaluator.clone())?;
+            let filter_value = evaluate_id_in_typed_value(filter_value, None, ScopeRelationType::resolve_id_default(), None, element_scope.clone(), evaluator.clone())?;
+            match filter_value {
+                TypedValue::BoolValue(true) => (),
+                TypedValue::BoolValue(false) => continue,
+                _ => return Err(TydiLangError::new(format!("the filter of array comprehension {} can only be a bool, get {}", exps.as_str(), filter_value.get_brief_info()), CodeLocation::new_unknown())),
+            }
+        }
+
+        let element_value = evaluate_expression(element_exp.clone(), None, element_scope.clone(), evaluator.clone())?;
+        let element_value = evaluate_id_in_typed_value(element_value, None, ScopeRelationType::resolve_id_default(), None, element_scope.clone(), evaluator.clone())?;
+        output.push(element_value);
+    }
+    return Ok(TypedValue::Array(output));
+}
+
 #[allow(non_snake_case)]
 pub fn evaluate_UnaryExp(exp: Pair<Rule>, scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<TypedValue, TydiLangError> {
     let mut exp_typed_value = TypedValue::UnknwonValue;
@@ -193,7 +353,9 @@ pub fn evaluate_UnaryExp(exp: Pair<Rule>, scope: Arc<RwLock<Scope>>, evaluator:
             _ => unreachable!()
         }
     }
-    
+    //the operand might be an identifier or a function call, e.g. !is_stream(T)
+    exp_typed_value = evaluate_id_in_typed_value(exp_typed_value, None, ScopeRelationType::resolve_id_default(), None, scope.clone(), evaluator.clone())?;
+
     #[allow(unused_assignments)]
     let mut output_typed_value = TypedValue::UnknwonValue;
     match unary_operator {
@@ -233,6 +395,7 @@ pub fn parse_IdentifierWithArgExp(id: Pair<Rule>, _scope: Arc<RwLock<Scope>>, _e
     let mut id_type = IdentifierType::Unknown;
     let mut id_name = generate_init_value();
     let mut template_exps = BTreeMap::new();
+    let mut named_template_exps = BTreeMap::new();
     for element in id.clone().into_inner().into_iter() {
         let rule = element.as_rule();
         match rule {
@@ -240,12 +403,24 @@ pub fn parse_IdentifierWithArgExp(id: Pair<Rule>, _scope: Arc<RwLock<Scope>>, _e
                 (id_name, id_type) = parse_Term_identifier(element)?;
             }
             Rule::Exp => {
+                if !named_template_exps.is_empty() {
+                    return Err(TydiLangError::new(format!("positional template argument {} cannot follow a named template argument", element.as_str()), CodeLocation::new_unknown()));
+                }
                 template_exps.insert(template_exps.len(), element.as_str().to_string());
             }
+            Rule::NamedTemplateExp => {
+                let mut named_exp = element.clone().into_inner();
+                let arg_name = named_exp.next().unwrap().as_str().to_string();
+                let arg_exp = named_exp.next().unwrap().as_str().to_string();
+                if named_template_exps.contains_key(&arg_name) {
+                    return Err(TydiLangError::new(format!("template argument {} is assigned more than once", arg_name), CodeLocation::new_unknown()));
+                }
+                named_template_exps.insert(arg_name, arg_exp);
+            }
             _ => unreachable!()
         }
     }
-    let output_id = Identifier::new(id_name, id_type, template_exps, CodeLocation::new_unknown());
+    let output_id = Identifier::new(id_name, id_type, template_exps, named_template_exps, CodeLocation::new_unknown());
     let output_typed_value = TypedValue::Identifier(output_id);
     return Ok(output_typed_value);
 }
@@ -279,7 +454,7 @@ pub fn parse_FunctionExp(id: Pair<Rule>) -> Result<(String, IdentifierType), Tyd
     for element in id.clone().into_inner().into_iter() {
         let rule = element.as_rule();
         match rule {
-            Rule::ID => {
+            Rule::ID | Rule::ConversionFunctionId => {
                 id_name = element.as_str().to_string();
             }
             Rule::Exp => {
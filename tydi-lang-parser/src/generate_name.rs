@@ -1,44 +1,84 @@
-use std::sync::{Arc, RwLock, atomic::AtomicUsize};
+use std::sync::{Arc, RwLock};
 use std::collections::BTreeMap;
 
 use crate::trait_common::GetName;
-use crate::{tydi_parser::*, util, tydi_memory_representation::{Variable, TypedValue}};
+use crate::evaluation::get_logic_type_value_signature;
+use crate::{tydi_parser::*, tydi_memory_representation::{Variable, TypedValue, Scope, SrcInfo}};
 
-static mut GENERATE_COUNTER: AtomicUsize = AtomicUsize::new(0);
-static mut TEMPLATE_INSTANCE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+// FNV-1a, generated names must be the same for every compilation, so we cannot use the randomly seeded std hasher
+fn get_stable_hash(text: &str) -> String {
+    let mut hash: u32 = 0x811c9dc5;
+    for byte in text.as_bytes() {
+        hash ^= *byte as u32;
+        hash = hash.wrapping_mul(0x01000193);
+    }
+    return format!("{:08x}", hash);
+}
+
+// a short tag of the source file, so generated names from different files never collide
+pub fn get_src_tag(src: &str) -> String {
+    return get_stable_hash(src);
+}
 
-pub fn generate_built_in_variable_name_from_span(src: &Pair<Rule>) -> String {
+// code parsed again during evaluation is tagged by the scope it is evaluated in, so the same code in different template instances gets different names
+pub fn get_evaluated_src_tag(src: &str, scope: Arc<RwLock<Scope>>) -> String {
+    return get_stable_hash(&format!("{}:{}", scope.read().unwrap().get_name(), src));
+}
+
+pub fn generate_built_in_variable_name_from_span(src: &Pair<Rule>, raw_src: &SrcInfo) -> String {
     let src_span = src.as_span();
     let start_pos = src_span.start_pos().pos();
     let end_pos = src_span.end_pos().pos();
-    let counter;
-    unsafe {
-        GENERATE_COUNTER.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-        counter = GENERATE_COUNTER.load(std::sync::atomic::Ordering::SeqCst);
-    }
-    // format!("generated_{}_{}_{}_{}", start_pos, end_pos, util::generate_random_str(8), counter)
-    format!("generated_{}_{}_{}", start_pos, end_pos, counter)
+    format!("generated_{}_{}_{}", start_pos, end_pos, raw_src.src_tag)
 }
 
-pub fn generate_template_instance_name(template_var: Arc<RwLock<Variable>>, template_exps: &BTreeMap<usize, TypedValue>) -> String {
-    let template_var_name = template_var.read().unwrap().get_name();
-    let mut arg_part = String::new();
-    for index in 0..template_exps.len() {
-        arg_part.push_str(&template_exps.get(&index).unwrap().get_brief_info());
+// logic types in expressions are parsed again during evaluation, so the same span can appear multiple times in a scope
+pub fn generate_built_in_variable_name_from_span_in_scope(src: &Pair<Rule>, raw_src: &SrcInfo, scope: Arc<RwLock<Scope>>) -> String {
+    let name = generate_built_in_variable_name_from_span(src, raw_src);
+    return generate_unique_name_in_scope(name, scope);
+}
+
+// append an index to the name if the name already exists in the scope, the evaluation order is deterministic so the index is too
+fn generate_unique_name_in_scope(name: String, scope: Arc<RwLock<Scope>>) -> String {
+    let scope_read = scope.read().unwrap();
+    let scope_variables = scope_read.get_variables_ref();
+    if !scope_variables.contains_key(&name) {
+        return name;
     }
-    let counter;
-    unsafe {
-        TEMPLATE_INSTANCE_COUNTER.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-        counter = TEMPLATE_INSTANCE_COUNTER.load(std::sync::atomic::Ordering::SeqCst);
+    let mut index: usize = 1;
+    while scope_variables.contains_key(&format!("{}_{}", name, index)) {
+        index += 1;
     }
-    format!("instance_{}_{}_{}", template_var_name, arg_part, counter)
+    return format!("{}_{}", name, index);
 }
 
-pub fn generate_template_instance_name_based_on_old_name(old_name: String, template_exps: &BTreeMap<usize, TypedValue>) -> String {
+// the name is built from the evaluated template arguments, the same expression (e.g. "T" inside another template) can have different values
+// logic types are named by a hash of their structure, because the names of logic types come from the source code rather than the value
+fn generate_template_arg_part(template_arg_values: &BTreeMap<usize, TypedValue>) -> String {
     let mut arg_part = String::new();
-    for index in 0..template_exps.len() {
-        arg_part.push_str(&template_exps.get(&index).unwrap().get_brief_info());
+    for index in 0..template_arg_values.len() {
+        let template_arg_value = template_arg_values.get(&index).unwrap();
+        let template_arg_name = match template_arg_value {
+            TypedValue::LogicTypeValue(_) | TypedValue::RefToVar(_) | TypedValue::Array(_) | TypedValue::Map(_) => {
+                let signature = get_logic_type_value_signature(template_arg_value);
+                if signature == template_arg_value.get_brief_info() { signature } else { format!("Type{}", get_stable_hash(&signature)) }
+            },
+            _ => template_arg_value.get_brief_info(),
+        };
+        arg_part.push_str(&template_arg_name);
     }
+    return arg_part;
+}
+
+pub fn generate_template_instance_name(template_var: Arc<RwLock<Variable>>, template_arg_values: &BTreeMap<usize, TypedValue>, scope: Arc<RwLock<Scope>>) -> String {
+    let template_var_name = template_var.read().unwrap().get_name();
+    let arg_part = generate_template_arg_part(template_arg_values);
+    //the same template can be expanded multiple times in a scope
+    generate_unique_name_in_scope(format!("instance_{}_{}", template_var_name, arg_part), scope)
+}
+
+pub fn generate_template_instance_name_based_on_old_name(old_name: String, template_arg_values: &BTreeMap<usize, TypedValue>) -> String {
+    let arg_part = generate_template_arg_part(template_arg_values);
 
     format!("instance_{}_{}", old_name, arg_part)
 }
@@ -1,2 +1,3 @@
 pub mod check_assert;
+pub mod check_clock_domain;
 pub mod sugaring_auto_insertion_duplicator_voider;
\ No newline at end of file
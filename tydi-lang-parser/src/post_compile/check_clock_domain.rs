@@ -0,0 +1,117 @@
+use std::sync::{Arc, RwLock};
+
+use crate::error::TydiLangError;
+use crate::tydi_memory_representation::{Scope, Project, GetScope, TypedValue, Net, Port, Attribute, TraitCodeLocationAccess, CodeLocation};
+use crate::evaluation::{Evaluator, EvaluationTrace, get_net_brief_info, get_port_brief_name};
+
+/// get the clock domain name of a port, None if the time domain is not evaluated
+pub fn get_port_clock_domain(port: Arc<RwLock<Port>>) -> Option<String> {
+    let mut time_domain_value = port.read().unwrap().get_time_domain().read().unwrap().get_value();
+    //the time domain might refer to a template argument, follow the reference chain
+    for _ in 0..1000 {
+        match time_domain_value {
+            TypedValue::RefToVar(var) => time_domain_value = var.read().unwrap().get_value(),
+            _ => break,
+        }
+    }
+    return match time_domain_value {
+        TypedValue::ClockDomainValue(domain) => Some(domain),
+        TypedValue::StringValue(domain) => Some(domain),
+        _ => None,
+    };
+}
+
+fn collect_nets_in_value(value: &TypedValue, output: &mut Vec<Arc<RwLock<Net>>>) {
+    match value {
+        TypedValue::Net(net) => {
+            if output.iter().any(|existing_net| Arc::ptr_eq(existing_net, net)) { return; }
+            output.push(net.clone());
+        },
+        TypedValue::Array(array) => {
+            for element in array {
+                collect_nets_in_value(element, output);
+            }
+        },
+        _ => (),
+    }
+}
+
+fn collect_nets_in_scope(scope: Arc<RwLock<Scope>>, output: &mut Vec<Arc<RwLock<Net>>>) {
+    let all_vars = scope.read().unwrap().get_variables();
+    for (_, var) in all_vars {
+        let var_value = var.read().unwrap().get_value();
+        match &var_value {
+            TypedValue::Implementation(implementation) => {
+                match implementation.read().unwrap().get_template_args() {
+                    Some(_) => continue,
+                    None => (),
+                }
+                let implementation_scope = implementation.read().unwrap().get_scope();
+                collect_nets_in_scope(implementation_scope.clone(), output);
+            },
+            _ => collect_nets_in_value(&var_value, output),
+        }
+    }
+}
+
+/// return the message and locations of a clock domain crossing, None if the net stays in one clock domain
+fn check_net(net: Arc<RwLock<Net>>) -> Option<(String, Vec<CodeLocation>)> {
+    if net.read().unwrap().get_attributes().contains(&Attribute::ClockDomainCrossing) {
+        return None;
+    }
+    let source_port = net.read().unwrap().get_source_port();
+    let sink_port = net.read().unwrap().get_sink_port();
+    let (source_port, sink_port) = match (source_port, sink_port) {
+        (Some(source_port), Some(sink_port)) => (source_port, sink_port),
+        _ => return None,   //the net is not evaluated
+    };
+    let source_domain = get_port_clock_domain(source_port.clone());
+    let sink_domain = get_port_clock_domain(sink_port.clone());
+    let (source_domain, sink_domain) = match (source_domain, sink_domain) {
+        (Some(source_domain), Some(sink_domain)) => (source_domain, sink_domain),
+        _ => return None,
+    };
+    if source_domain == sink_domain {
+        return None;
+    }
+
+    let message = format!("net ({}) crosses clock domains: source port {} is in {}, sink port {} is in {}",
+        get_net_brief_info(net.clone()), get_port_brief_name(source_port.clone()), source_domain, get_port_brief_name(sink_port.clone()), sink_domain);
+    let locations = vec![net.read().unwrap().get_code_location(), source_port.read().unwrap().get_code_location(), sink_port.read().unwrap().get_code_location()];
+    return Some((message, locations));
+}
+
+pub fn check_scope(scope: Arc<RwLock<Scope>>, _evaluator: Arc<RwLock<Evaluator>>) -> Result<(), TydiLangError> {
+    let mut nets = vec![];
+    collect_nets_in_scope(scope.clone(), &mut nets);
+
+    let mut messages = vec![];
+    let mut locations = vec![];
+    for net in nets {
+        match check_net(net.clone()) {
+            Some((message, net_locations)) => {
+                messages.push(message);
+                locations.extend(net_locations);
+            },
+            None => (),
+        }
+    }
+    if messages.len() > 0 {
+        return Err(TydiLangError::new_multiple_locations(format!("{}\nUse @ClockDomainCrossing to mark an intentional crossing", messages.join("\n")), locations));
+    }
+
+    return Ok(());
+}
+
+pub fn check_project(project: Arc<RwLock<Project>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<(), TydiLangError> {
+    evaluator.write().unwrap().add_trace(EvaluationTrace::new_region_begin(format!("check clock domain")));
+
+    let packages = project.read().unwrap().get_packages();
+    for (_, package) in &packages {
+        let package_scope = package.read().unwrap().get_scope();
+        check_scope(package_scope.clone(), evaluator.clone())?;
+    }
+
+    evaluator.write().unwrap().add_trace(EvaluationTrace::new_region_end(format!("check clock domain")));
+    return Ok(());
+}
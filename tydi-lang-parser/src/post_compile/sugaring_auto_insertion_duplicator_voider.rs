@@ -1,4 +1,4 @@
-use std::sync::{Arc, RwLock, atomic::AtomicUsize};
+use std::sync::{Arc, RwLock};
 use std::collections::BTreeMap;
 
 use crate::error::TydiLangError;
@@ -163,9 +163,6 @@ fn sugaring_add_duplicator_voider_for_single_implementation(project: Arc<RwLock<
         }
     }
 
-    static mut DUPLICATOR_COUNTER: AtomicUsize = AtomicUsize::new(0);
-    static mut VOIDER_COUNTER: AtomicUsize = AtomicUsize::new(0);
-
     for (_, (src_port_name, src_port)) in src_port_mapping.iter().enumerate() {
         let project_packages = project.read().unwrap().get_packages();
         let std_package = project_packages.get(STD_LIB_PACKAGE_NAME);
@@ -183,16 +180,11 @@ fn sugaring_add_duplicator_voider_for_single_implementation(project: Arc<RwLock<
 
         // append voider
         if sink_ports.len() == 0 {
-            let voider_counter;
-            unsafe {
-                VOIDER_COUNTER.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-                voider_counter = VOIDER_COUNTER.load(std::sync::atomic::Ordering::SeqCst);
-            }
             let src_port_owner = src_port_owner_mapping.get(src_port_name).expect("bug: src_port_name not found");
             // add voider instance
             let new_voider;
             {
-                let voider_name = format!("voider_{}", voider_counter);
+                let voider_name = generate_sugaring_instance_name(format!("voider"), src_port_owner, src_port.clone());
                 // resolve voider
                 let mut arg_map = BTreeMap::new();
                 let src_port_type_var = src_port.read().unwrap().get_logical_type();
@@ -220,18 +212,12 @@ fn sugaring_add_duplicator_voider_for_single_implementation(project: Arc<RwLock<
 
         // append duplicator
         if sink_ports.len() >= 2 {
-            let duplicator_counter;
-            unsafe {
-                DUPLICATOR_COUNTER.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
-                duplicator_counter = DUPLICATOR_COUNTER.load(std::sync::atomic::Ordering::SeqCst);
-            }
-
             let src_port_owner = src_port_owner_mapping.get(src_port_name).expect("bug: src_port_name not found");
 
             // add duplicator instance
             let new_duplicator;
             {
-                let duplicator_name = format!("duplicator_{}", duplicator_counter);
+                let duplicator_name = generate_sugaring_instance_name(format!("duplicator"), src_port_owner, src_port.clone());
                 // resolve duplicator
                 let mut arg_map = BTreeMap::new();
                 let src_port_type_var = src_port.read().unwrap().get_logical_type();
@@ -258,7 +244,23 @@ fn sugaring_add_duplicator_voider_for_single_implementation(project: Arc<RwLock<
                 let old_nets = src_to_sink_port_nets_mapping.get(src_port_name).expect("bug: src_port_name not found");
                 for single_old_net in old_nets {
                     let single_old_net_name = single_old_net.read().unwrap().get_name();
-                    all_vars_in_implementation_scope.remove(&single_old_net_name).expect("bug: single old net name not found");
+                    if all_vars_in_implementation_scope.remove(&single_old_net_name).is_some() {
+                        continue;
+                    }
+                    //nets expanded from a bulk net or a port bundle net are stored in an array
+                    let mut found_in_array = false;
+                    for (_, var) in &all_vars_in_implementation_scope {
+                        let var_value = var.read().unwrap().get_value();
+                        if let TypedValue::Array(array) = &var_value {
+                            let remaining_values: Vec<TypedValue> = array.iter().filter(|v| !matches!(v, TypedValue::Net(net) if Arc::ptr_eq(net, single_old_net))).cloned().collect();
+                            if remaining_values.len() != array.len() {
+                                var.write().unwrap().set_value(TypedValue::Array(remaining_values));
+                                found_in_array = true;
+                                break;
+                            }
+                        }
+                    }
+                    assert!(found_in_array, "bug: single old net name not found");
                 }
                 implementation_scope.write().unwrap().set_variables(all_vars_in_implementation_scope);
             }
@@ -312,4 +314,17 @@ fn sugaring_add_duplicator_voider_for_single_implementation(project: Arc<RwLock<
     }
 
     return Ok(());
-}
\ No newline at end of file
+}
+
+// the inserted instance is named after the port it is connected to, so the name is the same in every compilation
+fn generate_sugaring_instance_name(prefix: String, src_port_owner: &PortOwner, src_port: Arc<RwLock<Port>>) -> String {
+    let owner_name = match src_port_owner {
+        PortOwner::ImplInstance(inst) => inst.read().unwrap().get_name(),
+        _ => String::from("self"),
+    };
+    let port_name = match src_port.read().unwrap().get_id_in_scope() {
+        Some(id) => id,
+        None => src_port.read().unwrap().get_name(),
+    };
+    return format!("{}_{}_{}", prefix, owner_name, port_name);
+}
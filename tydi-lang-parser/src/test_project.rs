@@ -1,7 +1,27 @@
 #[cfg(test)]
 mod all_parse_test
 {
-    use crate::{tydi_memory_representation::Project, post_compile};
+    use std::sync::{Arc, RwLock};
+
+    use crate::{tydi_memory_representation::{Project, TypedValue, GetScope, GlobalIdentifier, PortOwner}, trait_common::GetName, post_compile};
+    use crate::evaluation::{resolve_logic_type_var, DiagnosticLevel};
+    use crate::error::TydiLangError;
+
+    // check that a step fails with an error message containing the expected text, the error is returned for further checks
+    fn expect_error<T>(result: Result<T, TydiLangError>, description: &str, expected_message: &str) -> TydiLangError {
+        match result {
+            Ok(_) => panic!("{} should be rejected", description),
+            Err(e) => {
+                assert!(e.message.contains(expected_message), "{}: {}", description, e.message);
+                return e;
+            },
+        }
+    }
+
+    fn check_evaluation_error(project: &Arc<RwLock<Project>>, package: &str, target: &str, expected_message: &str) -> TydiLangError {
+        let result = project.read().unwrap().evaluate_target(format!("{}", target), format!("{}", package));
+        return expect_error(result, target, expected_message);
+    }
 
     #[test]
     fn sample_project_0() {
@@ -1544,4 +1564,1571 @@ mod all_parse_test
         println!("{}", evaluator.read().unwrap().print_evaluation_record());
     }
 
+    #[test]
+    fn sample_project_net_type_check_0() {
+        let project = Project::new(format!("sample_project"));
+        {
+            let mut project_write = project.write().unwrap();
+    
+            let src_pack0 = String::from(r#"
+            package pack0;
+
+            bit8_stream = Stream(Bit(8), d=1);
+            bit8_stream_copy = Stream(Bit(8), d=1);
+            bit8_stream_d2 = Stream(Bit(8), d=2);
+
+            streamlet bypass {
+                in_port: bit8_stream in;
+                out_port: bit8_stream_copy out;
+                out_port_same: bit8_stream out;
+                out_port_d2: bit8_stream_d2 out;
+            }
+
+            impl bypass_structural of bypass {
+                self.in_port => self.out_port;
+            }
+
+            impl bypass_strict of bypass {
+                self.in_port => self.out_port @StrictTypeCheck;
+            }
+
+            impl bypass_strict_same_type of bypass {
+                self.in_port => self.out_port_same @StrictTypeCheck;
+            }
+
+            impl bypass_wrong_dimension of bypass {
+                self.in_port => self.out_port_d2;
+            }
+
+            impl bypass_no_type_check of bypass {
+                self.in_port => self.out_port_d2 @NoTypeCheck;
+            }
+            "#);
+    
+            let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        project.read().unwrap().evaluate_target(format!("bypass_structural"), format!("pack0")).expect("structurally equal types should be accepted");
+        project.read().unwrap().evaluate_target(format!("bypass_no_type_check"), format!("pack0")).expect("@NoTypeCheck should skip the type check");
+        project.read().unwrap().evaluate_target(format!("bypass_strict_same_type"), format!("pack0")).expect("the same type variable should be accepted in strict mode");
+
+        check_evaluation_error(&project, "pack0", "bypass_strict", "connects incompatible logic types (strict check)");
+
+        let e = check_evaluation_error(&project, "pack0", "bypass_wrong_dimension", "dimension");
+        assert!(e.location.len() == 3);
+    }
+
+    #[test]
+    fn sample_project_net_type_check_1() {
+        let project = Project::new(format!("sample_project"));
+        {
+            let mut project_write = project.write().unwrap();
+    
+            let src_pack0 = String::from(r#"
+            package pack0;
+
+            Group rgb {
+                r: Bit(8);
+                g: Bit(8);
+                b: Bit(8);
+            }
+            Group rgb_wide {
+                r: Bit(8);
+                g: Bit(10);
+                b: Bit(8);
+            }
+            rgb_stream = Stream(rgb);
+            rgb_wide_stream = Stream(rgb_wide);
+
+            streamlet rgb_in_s {
+                in_port: rgb_stream in;
+            }
+            impl rgb_in_i of rgb_in_s @External {}
+
+            streamlet rgb_out_s {
+                out_port: rgb_wide_stream out;
+            }
+            impl rgb_out_i of rgb_out_s @External {}
+
+            streamlet top_s {}
+            impl top_i of top_s {
+                instance src(rgb_out_i);
+                instance sink(rgb_in_i);
+                src.out_port => sink.in_port;
+            }
+            "#);
+    
+            let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        check_evaluation_error(&project, "pack0", "top_i", "(Group).g");
+    }
+
+    #[test]
+    fn sample_project_net_direction_check_0() {
+        let project = Project::new(format!("sample_project"));
+        {
+            let mut project_write = project.write().unwrap();
+    
+            let src_pack0 = String::from(r#"
+            package pack0;
+
+            bit8_stream = Stream(Bit(8));
+
+            streamlet bypass {
+                in_port: bit8_stream in;
+                out_port: bit8_stream out;
+            }
+            impl bypass_i of bypass {
+                self.in_port => self.out_port;
+            }
+
+            impl self_out_as_source of bypass {
+                self.out_port => self.in_port;
+            }
+
+            impl instance_in_as_source of bypass {
+                instance child(bypass_i);
+                child.in_port => self.out_port;
+            }
+
+            impl instance_out_as_sink of bypass {
+                instance child(bypass_i);
+                self.in_port => child.out_port;
+            }
+
+            impl instance_to_instance of bypass {
+                instance child0(bypass_i);
+                instance child1(bypass_i);
+                self.in_port => child0.in_port;
+                child0.out_port => child1.in_port;
+                child1.out_port => self.out_port;
+            }
+            "#);
+    
+            let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        project.read().unwrap().evaluate_target(format!("instance_to_instance"), format!("pack0")).expect("fail to evaluate");
+
+        for (target, expected_message) in [("self_out_as_source", "as source"), ("instance_in_as_source", "as source"), ("instance_out_as_sink", "as sink")] {
+            let e = check_evaluation_error(&project, "pack0", target, expected_message);
+            assert!(e.location.len() == 2);
+        }
+    }
+
+    #[test]
+    fn sample_project_clock_domain_crossing_0() {
+        let project = Project::new(format!("sample_project"));
+        {
+            let mut project_write = project.write().unwrap();
+    
+            let src_pack0 = String::from(r#"
+            package pack0;
+
+            bit8_stream = Stream(Bit(8));
+            clk_b: clock = "clk_b";
+            clk_missing: clock;
+
+            streamlet bypass<clk: clock> {
+                in_port: bit8_stream in /clk;
+                out_port: bit8_stream out /clk;
+            }
+            impl bypass_i<clk: clock> of bypass<clk> {
+                self.in_port => self.out_port;
+            }
+
+            streamlet top {
+                in_port: bit8_stream in /"clk_a";
+                out_port: bit8_stream out /"clk_b";
+            }
+            impl top_crossing of top {
+                instance a(bypass_i<"clk_a">);
+                instance b(bypass_i<clk_b>);
+                self.in_port => a.in_port;
+                a.out_port => b.in_port;
+                b.out_port => self.out_port;
+            }
+            impl top_marked_crossing of top {
+                instance a(bypass_i<"clk_a">);
+                instance b(bypass_i<clk_b>);
+                self.in_port => a.in_port;
+                a.out_port => b.in_port @ClockDomainCrossing;
+                b.out_port => self.out_port;
+            }
+            "#);
+    
+            let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        let evaluator = project.read().unwrap().evaluate_target(format!("top_marked_crossing"), format!("pack0")).expect("fail to evaluate");
+        post_compile::check_clock_domain::check_project(project.clone(), evaluator.clone()).expect("marked crossing should pass");
+
+        let evaluator = project.read().unwrap().evaluate_target(format!("top_crossing"), format!("pack0")).expect("fail to evaluate");
+        let check_result = post_compile::check_clock_domain::check_project(project.clone(), evaluator.clone());
+        let e = expect_error(check_result, "clock domain crossing", "a.out_port => b.in_port");
+        assert!(e.message.contains("clk_a") && e.message.contains("clk_b"));
+        assert!(e.location.len() == 3);
+
+        check_evaluation_error(&project, "pack0", "clk_missing", "clock domain variable clk_missing has no value");
+    }
+
+    #[test]
+    fn sample_project_logic_type_array_0() {
+        let project = Project::new(format!("sample_project"));
+        {
+            let mut project_write = project.write().unwrap();
+    
+            let src_pack0 = String::from(r#"
+            package pack0;
+
+            n = 4;
+            bytes = Bit(8)[n];
+            Group word {
+                lanes: Bit(8)[n];
+            }
+            word_streams = Stream(word)[2];
+            empty = Bit(8)[0];
+
+            streamlet good_s {
+                in_port: word_streams[0] in;
+                out_port: word_streams[1] out;
+                byte_port: Stream(bytes[3]) out;
+            }
+            impl good_i of good_s {
+                self.in_port => self.out_port;
+            }
+
+            streamlet array_port_s {
+                in_port: word_streams in;
+            }
+            impl array_port_i of array_port_s {}
+
+            streamlet array_stream_type_s {
+                in_port: Stream(bytes) in;
+            }
+            impl array_stream_type_i of array_stream_type_s {}
+
+            streamlet index_out_of_range_s {
+                in_port: Stream(bytes[4]) in;
+            }
+            impl index_out_of_range_i of index_out_of_range_s {}
+
+            streamlet empty_array_s {
+                in_port: Stream(empty[0]) in;
+            }
+            impl empty_array_i of empty_array_s {}
+            "#);
+    
+            let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        project.read().unwrap().evaluate_target(format!("good_i"), format!("pack0")).expect("fail to evaluate");
+        {
+            let package = project.read().unwrap().get_packages()[&format!("pack0")].clone();
+            let bytes = package.read().unwrap().get_scope().read().unwrap().get_variables()[&format!("bytes")].clone();
+            match resolve_logic_type_var(bytes).read().unwrap().get_value() {
+                TypedValue::Array(array) => assert!(array.len() == 4),
+                v => panic!("bytes should be an array, found {}", v.get_brief_info()),
+            }
+        }
+
+        for (target, expected_message) in [("array_port_i", "cannot be a logic type array"), ("array_stream_type_i", "cannot be a logic type array"), ("index_out_of_range_i", "out of range"), ("empty_array_i", "must be larger than 0")] {
+            check_evaluation_error(&project, "pack0", target, expected_message);
+        }
+    }
+
+    #[test]
+    fn sample_project_array_range_0() {
+        let project = Project::new(format!("sample_project"));
+        {
+            let mut project_write = project.write().unwrap();
+    
+            let src_pack0 = String::from(r#"
+            package pack0;
+
+            n = 3;
+            Group bits<count: int> {
+                for w in [1..=count] {
+                    data: Bit(w);
+                }
+                assert([1..=count] == [1,2,3]);
+            }
+            bits_stream = Stream(bits<n>);
+
+            streamlet bypass_s {
+                in_port: bits_stream in;
+                out_port: bits_stream out;
+                for i in [n..0:-1] {
+                    assert(i > 0 && i <= n);
+                }
+                assert([0..=6:2] == [0,2,4,6]);
+            }
+            impl bypass_i of bypass_s {
+                self.in_port => self.out_port;
+            }
+            "#);
+    
+            let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        let evaluator = project.read().unwrap().evaluate_target(format!("bypass_i"), format!("pack0")).expect("fail to evaluate");
+        post_compile::check_assert::check_project(project.clone(), evaluator.clone()).expect("assertion failed");
+    }
+
+    #[test]
+    fn sample_project_access_property_0() {
+        let project = Project::new(format!("sample_project"));
+        {
+            let mut project_write = project.write().unwrap();
+    
+            let src_pack0 = String::from(r#"
+            package pack0;
+
+            bit8 = Bit(8);
+            Group pair {
+                a: Bit(8);
+                b: Bit(4);
+            }
+            Union choice {
+                a: Bit(8);
+                b: Bit(4);
+                c: Bit(2);
+            }
+            pair_stream = Stream(pair, d=2, t=2.0, c=4);
+            clk_a: clock = "clk_a";
+
+            streamlet bypass_s<clk: clock> {
+                in_port: pair_stream in /clk;
+                out_port: pair_stream out /clk;
+            }
+            impl bypass_i<clk: clock> of bypass_s<clk> {
+                self.in_port => self.out_port;
+            }
+
+            streamlet top_s {
+                in_port: pair_stream in /"clk_a";
+                out_port: pair_stream out /"clk_a";
+            }
+            impl top_i of top_s {
+                instance inst(bypass_i<clk_a>);
+                instance inst_copy(inst->implementation);
+                self.in_port => inst.in_port;
+                inst.out_port => inst_copy.in_port;
+                inst_copy.out_port => self.out_port;
+
+                assert(bit8->width == 8);
+                assert(pair_stream->dimension == 2 && pair_stream->d == 2 && pair_stream->throughput == 2.0 && pair_stream->complexity == 4);
+                assert(pair->count == 2 && choice->count == 3);
+                assert(inst.in_port->direction == "In" && self.out_port->direction == "Out");
+                assert(inst.in_port->clock_domain == clk_a && self.in_port->clock_domain == clk_a);
+                assert(inst_copy.out_port->clock_domain == clk_a);
+            }
+
+            streamlet unknown_property_s {}
+            impl unknown_property_i of unknown_property_s {
+                assert(bit8->dimension == 1);
+            }
+            "#);
+    
+            let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        project.read().unwrap().evaluate_target(format!("top_i"), format!("pack0")).expect("fail to evaluate");
+
+        let e = check_evaluation_error(&project, "pack0", "unknown_property_i", "doesn't have property dimension");
+        assert!(e.message.contains("width"));
+    }
+
+    #[test]
+    fn sample_project_function_through_package_0() {
+        let project = Project::new(format!("sample_project"));
+        {
+            let mut project_write = project.write().unwrap();
+
+            let src_std = String::from(r#"
+            package std;
+
+            width = 4;
+            "#);
+
+            let src_pack0 = String::from(r#"
+            package pack0;
+            use std;
+
+            count = 3;
+            indices = std.range(count);
+            names = std.toString(std.width);
+
+            streamlet top_s {}
+            impl top_i of top_s {
+                assert(indices == [0, 1, 2]);
+                assert(names == "4");
+                assert(std.range(std.width) == [0, 1, 2, 3]);
+            }
+
+            impl not_function_i of top_s {
+                assert(std.width(1) == 4);
+            }
+            "#);
+
+            let status = project_write.add_package(format!("./std.td"), src_std);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+            let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        project.read().unwrap().evaluate_target(format!("top_i"), format!("pack0")).expect("fail to evaluate");
+
+        check_evaluation_error(&project, "pack0", "not_function_i", "width is not a function");
+    }
+
+    #[test]
+    fn sample_project_user_function_0() {
+        let project = Project::new(format!("sample_project"));
+        {
+            let mut project_write = project.write().unwrap();
+
+            let src_pack1 = String::from(r#"
+            package pack1;
+
+            bits_per_byte = 8;
+            #the number of bits of n bytes#
+            fn bits_of(n: int) -> int = n * bits_per_byte;
+            fn sum_first_two(widths: [int]) -> int {
+                total = widths[0] + widths[1];
+                assert(total > 0, "widths must be positive");
+                return total;
+            }
+            "#);
+
+            let src_pack0 = String::from(r#"
+            package pack0;
+            use pack1;
+
+            fn double(x: int) -> int = x * 2;
+            fn describe(x: int) -> string = toString(double(x));
+            fn no_return_type(x: int) = x + 1;
+            fn not_int() -> int = "text";
+            fn forever(x: int) -> int = forever(x + 1);
+            fn countdown(x: int) -> int = x == 0 ? 0 : countdown(x - 1);
+
+            width = pack1.bits_of(2);
+            data_bits = Bit(double(4));
+            data_stream = Stream(data_bits);
+
+            streamlet top_s {
+                in_port: data_stream in;
+                out_port: data_stream out;
+            }
+            impl top_i of top_s {
+                self.in_port => self.out_port;
+
+                assert(width == 16);
+                assert(double(width) == 32);
+                assert(describe(3) == "6");
+                assert(no_return_type(1) == 2);
+                assert(pack1.sum_first_two([3..=5]) == 7);
+                assert(data_bits->width == 8);
+                assert(countdown(63) == 0, "the maximum call deepth");
+            }
+
+            streamlet empty_s {}
+            impl wrong_return_i of empty_s {
+                assert(not_int() == 1);
+            }
+            impl wrong_arg_type_i of empty_s {
+                assert(double("a") == 1);
+            }
+            impl wrong_arg_count_i of empty_s {
+                assert(double(1, 2) == 1);
+            }
+            impl recursion_i of empty_s {
+                assert(forever(0) == 1);
+            }
+            impl failed_assert_in_function_i of empty_s {
+                assert(pack1.sum_first_two([0, 0]) == 0);
+            }
+            "#);
+
+            let status = project_write.add_package(format!("./pack1.td"), src_pack1);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+            let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        project.read().unwrap().evaluate_target(format!("top_i"), format!("pack0")).expect("fail to evaluate");
+
+        let error_cases = vec![
+            ("wrong_return_i", "function not_int should return int"),
+            ("wrong_arg_type_i", "function double, argument x expects int"),
+            ("wrong_arg_count_i", "function double has 1 argument(s), get 2"),
+            ("recursion_i", "function forever exceeds the maximum call deepth"),
+            ("failed_assert_in_function_i", "widths must be positive"),
+        ];
+        for (target, expected_message) in error_cases {
+            check_evaluation_error(&project, "pack0", target, expected_message);
+        }
+    }
+
+    #[test]
+    fn sample_project_math_function_in_template_0() {
+        let project = Project::new(format!("sample_project"));
+        {
+            let mut project_write = project.write().unwrap();
+
+            let src_pack0 = String::from(r#"
+            package pack0;
+
+            Group indexed<n: int> {
+                index: Bit(clog2(n));
+                data: Bit(max(8, pow(2, clog2(n))));
+            }
+            indexed_5 = indexed<5>;
+            indexed_100 = indexed<100>;
+
+            streamlet top_s {}
+            impl top_i of top_s {
+                assert(indexed_5.index->width == 3 && indexed_5.data->width == 8);
+                assert(indexed_100.index->width == 7 && indexed_100.data->width == 128);
+            }
+            "#);
+
+            let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        project.read().unwrap().evaluate_target(format!("top_i"), format!("pack0")).expect("fail to evaluate");
+    }
+
+    #[test]
+    fn sample_project_array_function_on_ports_0() {
+        let project = Project::new(format!("sample_project"));
+        {
+            let mut project_write = project.write().unwrap();
+
+            let src_pack0 = String::from(r#"
+            package pack0;
+
+            lanes = [1, 2, 4, 2];
+            streamlet split_s {
+                input: Stream(Bit(8)) in;
+                for i in unique(lanes) {
+                    output_port: Stream(Bit(8 * i)) out;
+                }
+            }
+            impl split_i of split_s {
+                assert(len(self.output_port) == 3);
+                assert(sum(unique(lanes)) == 7 && index_of(lanes, 4) == 2);
+            }
+            "#);
+
+            let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        project.read().unwrap().evaluate_target(format!("split_i"), format!("pack0")).expect("fail to evaluate");
+    }
+
+    #[test]
+    fn sample_project_string_function_0() {
+        let project = Project::new(format!("sample_project"));
+        {
+            let mut project_write = project.write().unwrap();
+
+            let src_pack0 = String::from(r#"
+            package pack0;
+
+            streamlet lane_s<lane: int, domain: string> {
+                input: Stream(Bit(8)) in /format("{}_{}", domain, lane);
+                output: Stream(Bit(8)) out /format("{}_{}", domain, lane);
+            }
+            impl lane_i<lane: int, domain: string> of lane_s<lane, domain> {
+                name = join([upper(replace(domain, "k", "ck")), toString(lane)], "_");
+                assert(name == format("{}_{}", upper(replace(domain, "k", "ck")), lane), "name");
+                assert(starts_with(name, "CLCK") && substring(name, 5) == "3");
+                self.input => self.output;
+            }
+            impl top_i of lane_s<3, "clk"> {
+                instance lane(lane_i<3, "clk">);
+                self.input => lane.input;
+                lane.output => self.output;
+            }
+            "#);
+
+            let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        project.read().unwrap().evaluate_target(format!("top_i"), format!("pack0")).expect("fail to evaluate");
+        let json_output = project.read().unwrap().get_pretty_json();
+        assert!(json_output.contains("clk_3"));
+    }
+
+    #[test]
+    fn sample_project_type_introspection_0() {
+        let project = Project::new(format!("sample_project"));
+        {
+            let mut project_write = project.write().unwrap();
+
+            let src_pack0 = String::from(r#"
+            package pack0;
+
+            Group pixel {
+                r: Bit(8);
+                g: Bit(8);
+                b: Bit(4);
+            }
+            Union choice {
+                a: Bit(3);
+                p: pixel;
+                c: Bit(1);
+            }
+            pixel_stream = Stream(pixel);
+            Group with_stream {
+                x: Bit(2);
+                y: pixel_stream;
+            }
+            nibbles = Bit(4)[3];
+
+            Group serialized<T: type> {
+                data: Bit(bit_width(T));
+                names = field_names(T);
+                first: field_type(T, names[0]);
+            }
+            serialized_pixel = serialized<pixel>;
+
+            streamlet top_s {}
+            impl top_i of top_s {
+                assert(bit_width(pixel) == 20 && bit_width(nibbles) == 12, "width");
+                assert(field_names(pixel) == ["r", "g", "b"], "field names");
+                assert(field_type(pixel, "b")->width == 4, "field type");
+                assert(bit_width(choice) == 22 && element_count(choice) == 3, "union");
+                assert(bit_width(with_stream) == 2, "nested stream");
+                assert(is_stream(pixel_stream) && !is_stream(pixel), "is_stream");
+                assert(is_group(pixel) && !is_group(choice), "is_group");
+                assert(serialized_pixel.data->width == 20 && serialized_pixel.first->width == 8, "template");
+            }
+
+            impl stream_width_i of top_s {
+                assert(bit_width(pixel_stream) > 0);
+            }
+            impl missing_field_i of top_s {
+                assert(field_type(pixel, "a")->width > 0);
+            }
+            impl bit_count_i of top_s {
+                assert(element_count(Bit(8)) > 0);
+            }
+            impl int_names_i of top_s {
+                assert(len(field_names(8)) > 0);
+            }
+            "#);
+
+            let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        project.read().unwrap().evaluate_target(format!("top_i"), format!("pack0")).expect("fail to evaluate");
+
+        for (target, expected_message) in [("stream_width_i", "cannot be applied to a Stream"), ("missing_field_i", "doesn't have field a, available: [\"r\", \"g\", \"b\"]"), ("bit_count_i", "can only be a Group or a Union"), ("int_names_i", "argument 0 of field_names can only be a logic type")] {
+            check_evaluation_error(&project, "pack0", target, expected_message);
+        }
+    }
+
+    #[test]
+    fn sample_project_conversion_function_0() {
+        let project = Project::new(format!("sample_project"));
+        {
+            let mut project_write = project.write().unwrap();
+
+            let src_pack0 = String::from(r#"
+            package pack0;
+
+            Group configured<width: string, ratio: string> {
+                data: Bit(int(width));
+                user: Bit(int(int(width) * float(ratio), "ceil"));
+            }
+            configured_data = configured<"12", "0.3">;
+
+            streamlet top_s {}
+            impl top_i of top_s {
+                assert(configured_data.data->width == 12 && configured_data.user->width == 4);
+            }
+            impl bad_width_i of top_s {
+                assert(int("twelve") > 0);
+            }
+            "#);
+
+            let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        project.read().unwrap().evaluate_target(format!("top_i"), format!("pack0")).expect("fail to evaluate");
+
+        let e = check_evaluation_error(&project, "pack0", "bad_width_i", "int cannot parse \"twelve\" as an integer");
+        let location = &e.location[0];
+        let located_src = &location.src_file.file_content[location.begin.expect("the error should point to the function call") .. location.end.unwrap()];
+        assert!(located_src.contains("int(\"twelve\")"), "{}", located_src);
+    }
+
+    #[test]
+    fn sample_project_diagnostics_0() {
+        let project = Project::new(format!("sample_project"));
+        {
+            let mut project_write = project.write().unwrap();
+
+            let src_pack0 = String::from(r#"
+            package pack0;
+
+            Group word<n: int> {
+                print("instantiate word with", n);
+                data: Bit(n);
+            }
+            Group legacy_word<n: int> {
+                warn(format("legacy_word is deprecated, use word<{}>", n));
+                data: Bit(n);
+            }
+            word_8 = word<8>;
+            legacy_16 = legacy_word<16>;
+
+            streamlet top_s {}
+            impl top_i of top_s {
+                assert(word_8.data->width == 8 && legacy_16.data->width == 16);
+            }
+            impl bad_i of top_s {
+                print("checking bad_i");
+                error("first problem");
+                error("second problem:", 2);
+            }
+            "#);
+
+            let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        let evaluator = project.read().unwrap().evaluate_target(format!("top_i"), format!("pack0")).expect("fail to evaluate");
+        let diagnostics = evaluator.read().unwrap().get_diagnostics();
+        assert!(diagnostics.len() == 2);
+        assert!(diagnostics[0].level == DiagnosticLevel::Print && diagnostics[0].message == "instantiate word with 8");
+        assert!(diagnostics[1].level == DiagnosticLevel::Warning && diagnostics[1].message == "legacy_word is deprecated, use word<16>");
+        let location = &diagnostics[1].location;
+        assert!(location.src_file.file_content[location.begin.unwrap() .. location.end.unwrap()].contains("warn("));
+        assert!(evaluator.read().unwrap().print_diagnostics().contains("[warning] legacy_word is deprecated"));
+
+        //error doesn't stop the evaluation, all errors are reported at the end
+        let evaluator = project.read().unwrap().new_evaluator();
+        let result = project.read().unwrap().evaluate_target_with_evaluator(format!("bad_i"), format!("pack0"), evaluator.clone());
+        let e = expect_error(result, "bad_i", "2 error(s) reported during evaluation:\nfirst problem\nsecond problem: 2");
+        assert!(e.location.len() == 2);
+        let diagnostics = evaluator.read().unwrap().get_diagnostics();
+        assert!(diagnostics.len() == 3 && diagnostics[0].message == "checking bad_i");
+    }
+
+    #[test]
+    fn sample_project_conditional_expression_0() {
+        let project = Project::new(format!("sample_project"));
+        {
+            let mut project_write = project.write().unwrap();
+
+            let src_pack0 = String::from(r#"
+            package pack0;
+
+            wide = true;
+            T = wide ? Bit(64) : Bit(32);
+            Group lane<wide: bool> {
+                data: if wide then Bit(64) else Bit(32);
+                valid: Bit(1);
+            }
+            lane_narrow = lane<false>;
+            lane_wide = lane<true>;
+            lane_stream = Stream(wide ? lane_wide : lane_narrow);
+            depth = !wide ? int("x") : 4;
+
+            streamlet top_s {
+                input: lane_stream in;
+            }
+            impl top_i of top_s {
+                assert(T->width == 64);
+                assert(lane_narrow.data->width == 32 && lane_wide.data->width == 64);
+                assert(bit_width(lane_narrow) == 33 && bit_width(field_type(lane_wide, "data")) == 64);
+                assert(depth == 4);
+            }
+            "#);
+
+            let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        project.read().unwrap().evaluate_target(format!("top_i"), format!("pack0")).expect("fail to evaluate");
+    }
+
+    #[test]
+    fn sample_project_array_comprehension_0() {
+        let project = Project::new(format!("sample_project"));
+        {
+            let mut project_write = project.write().unwrap();
+
+            let src_pack0 = String::from(r#"
+            package pack0;
+
+            widths = [w for w in [4, 8, 12, 16] if w % 8 == 0];
+            lanes = [Bit(w) for w in widths];
+            lane_a = lanes[0];
+            lane_b = lanes[1];
+            Group packed {
+                a: lane_a;
+                b: lane_b;
+            }
+            lane_names = [format("lane_{}", i) for i in range(len(lanes))];
+
+            streamlet top_s {}
+            impl top_i of top_s {
+                assert(widths == [8, 16], "widths");
+                assert(len(lanes) == 2 && lanes[1]->width == 16, "lanes");
+                assert([lane->width for lane in lanes] == widths, "widths of lanes");
+                assert(bit_width(packed) == 24, "packed");
+                assert(lane_names == ["lane_0", "lane_1"], "names");
+            }
+            "#);
+
+            let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        project.read().unwrap().evaluate_target(format!("top_i"), format!("pack0")).expect("fail to evaluate");
+    }
+
+    #[test]
+    fn sample_project_map_template_arg_0() {
+        let project = Project::new(format!("sample_project"));
+        {
+            let mut project_write = project.write().unwrap();
+
+            let src_pack0 = String::from(r#"
+            package pack0;
+
+            mem_cfg = {width: 32, depth: 1024, "latency": 2};
+            small_cfg: map = {width: 8, depth: 16, latency: 1};
+
+            Group mem_req<cfg: map> {
+                addr: Bit(clog2(cfg["depth"]));
+                data: Bit(cfg["width"]);
+            }
+            streamlet mem_s<cfg: map> {
+                req: Stream(mem_req<cfg>) in;
+                resp: Stream(Bit(cfg["width"])) out;
+            }
+            impl mem_i<cfg: map> of mem_s<cfg> {
+                assert(cfg["latency"] > 0);
+                self.req => self.resp @NoTypeCheck;
+            }
+            impl top_i of mem_s<mem_cfg> {
+                instance mem(mem_i<mem_cfg>);
+                self.req => mem.req;
+                mem.resp => self.resp;
+
+                for key in small_cfg {
+                    small_value = small_cfg[key];
+                }
+                key_name = "width";
+                assert(small_value == [16, 1, 8], "for over map");
+                assert(mem_cfg[key_name] == 32 && mem_cfg["depth"] * 2 == 2048, "index");
+                assert(mem_req<small_cfg>.addr->width == 4 && mem_req<mem_cfg>.addr->width == 10, "template");
+            }
+            impl missing_key_i of mem_s<mem_cfg> {
+                assert(mem_cfg["size"] > 0);
+            }
+            impl wrong_arg_i of mem_s<mem_cfg> {
+                instance mem(mem_i<32>);
+            }
+            "#);
+
+            let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        project.read().unwrap().evaluate_target(format!("top_i"), format!("pack0")).expect("fail to evaluate");
+
+        check_evaluation_error(&project, "pack0", "missing_key_i", "map {mem_cfg} doesn't have key \"size\", available keys: depth, latency, width");
+        check_evaluation_error(&project, "pack0", "wrong_arg_i", "expected map, get Int(32)");
+    }
+
+    #[test]
+    fn sample_project_default_named_template_args_0() {
+        let project = Project::new(format!("sample_project"));
+        {
+            let mut project_write = project.write().unwrap();
+
+            let src_pack0 = String::from(r#"
+            package pack0;
+
+            Group pair<T: type, N: int = 1, M: int = (N * 2)> {
+                data: T;
+                count: Bit(N);
+                total: Bit(M);
+            }
+            streamlet dup_s<T: type, N: int = 2> {
+                input: Stream(T) in;
+                output: Stream(Bit(N)) out;
+            }
+            impl dup_i<T: type, N: int = 2> of dup_s<T, N> {
+                self.input => self.output @NoTypeCheck;
+            }
+            impl top_i of dup_s<Bit(8)> {
+                instance dup0(dup_i<Bit(8)>);
+                instance dup1(dup_i<Bit(2), N=8>);
+                instance dup2(dup_i<N=2, T=Bit(8)>);
+                self.input => dup0.input;
+                dup0.output => dup1.input;
+                dup1.output => dup2.input;
+                dup2.output => self.output;
+
+                assert(bit_width(field_type(pair<Bit(4)>, "count")) == 1 && bit_width(field_type(pair<Bit(4)>, "total")) == 2, "default");
+                assert(bit_width(field_type(pair<Bit(4), M=5>, "count")) == 1 && bit_width(field_type(pair<Bit(4), M=5>, "total")) == 5, "named");
+                assert(bit_width(field_type(pair<Bit(4), 3>, "total")) == 6, "default refers to an earlier arg");
+            }
+            impl unknown_name_i of dup_s<Bit(8)> {
+                instance dup0(dup_i<Bit(8), K=2>);
+            }
+            impl twice_i of dup_s<Bit(8)> {
+                instance dup0(dup_i<Bit(8), T=Bit(8)>);
+            }
+            impl missing_i of dup_s<Bit(8)> {
+                instance dup0(dup_i<N=2>);
+            }
+            "#);
+
+            let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        project.read().unwrap().evaluate_target(format!("top_i"), format!("pack0")).expect("fail to evaluate");
+
+        check_evaluation_error(&project, "pack0", "unknown_name_i", "variable dup_i has no template argument named K");
+        check_evaluation_error(&project, "pack0", "twice_i", "template argument T of variable dup_i is given both by position and by name");
+        check_evaluation_error(&project, "pack0", "missing_i", "template argument T of variable dup_i is not given and has no default value");
+
+        let project = Project::new(format!("sample_project"));
+        let status = project.write().unwrap().add_package(format!("./pack1.td"), String::from(r#"
+            package pack1;
+            Group g<N: int = 1, M: int> {
+                data: Bit(M);
+            }
+            "#));
+        expect_error(status, "an argument without default after a default", "template argument M must have a default value because it follows an argument with a default value");
+    }
+
+    #[test]
+    fn sample_project_template_arg_constraints_0() {
+        let project = Project::new(format!("sample_project"));
+        {
+            let mut project_write = project.write().unwrap();
+
+            let src_pack0 = String::from(r#"
+            package pack0;
+
+            Group word<N: int where N > 0 && N <= 64> {
+                data: Bit(N);
+            }
+            streamlet pass_s<T: type is Stream> {
+                input: T in;
+                output: T out;
+            }
+            impl pass_i<T: type is Stream, lanes: int where lanes % 2 == 0 = 2> of pass_s<T> {
+                self.input => self.output;
+            }
+            impl top_i of pass_s<Stream(word<8>)> {
+                instance pass(pass_i<Stream(word<8>)>);
+                self.input => pass.input;
+                pass.output => self.output;
+                assert(bit_width(word<64>) == 64, "upper bound");
+            }
+            impl out_of_range_i of pass_s<Stream(word<8>)> {
+                assert(bit_width(word<65>) == 65);
+            }
+            impl wrong_kind_i of pass_s<Stream(word<8>)> {
+                instance pass(pass_i<word<8>>);
+            }
+            impl odd_lanes_i of pass_s<Stream(word<8>)> {
+                instance pass(pass_i<Stream(word<8>), lanes=3>);
+            }
+            "#);
+
+            let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        project.read().unwrap().evaluate_target(format!("top_i"), format!("pack0")).expect("fail to evaluate");
+
+        let e = check_evaluation_error(&project, "pack0", "out_of_range_i", "template argument N of variable word doesn't satisfy the constraint (N > 0 && N <= 64), get Int(65)");
+        //the instantiation site and the declaration of the argument
+        assert!(e.location.len() == 2 && e.location.iter().all(|location| location.begin.is_some()), "{:?}", e.location);
+        let e = check_evaluation_error(&project, "pack0", "wrong_kind_i", "template argument T of variable pass_i must be a Stream, get LogicGroup");
+        assert!(e.location.len() == 2 && e.location.iter().all(|location| location.begin.is_some()), "{:?}", e.location);
+        check_evaluation_error(&project, "pack0", "odd_lanes_i", "template argument lanes of variable pass_i doesn't satisfy the constraint (lanes % 2 == 0), get Int(3)");
+
+        let project = Project::new(format!("sample_project"));
+        let status = project.write().unwrap().add_package(format!("./pack1.td"), String::from(r#"
+            package pack1;
+            Group g<N: int is Stream> {
+                data: Bit(N);
+            }
+            "#));
+        expect_error(status, "a kind constraint on an int argument", "template argument N is not a type, so it cannot have a logic type constraint");
+    }
+
+    #[test]
+    fn sample_project_typed_array_0() {
+        let project = Project::new(format!("sample_project"));
+        {
+            let mut project_write = project.write().unwrap();
+
+            let src_pack0 = String::from(r#"
+            package pack0;
+
+            widths: [int] = [8, 16];
+            lanes: [type] = [Bit(8), Bit(4)];
+            clks: [clock] = ["clk_a", "clk_b"];
+            doubled: [int] = [w * 2 for w in widths];
+
+            streamlet s {
+                input: Stream(Bit(8)) in;
+                output: Stream(Bit(8)) out;
+            }
+            impl check_i<ws: [int], ts: [type], cs: [clock]> of s {
+                assert(ws[0] + bit_width(ts[1]) == 12 && len(cs) == 2, "template");
+                self.input => self.output;
+            }
+            impl top_i of s {
+                instance check(check_i<widths, lanes, clks>);
+                instance range_check(check_i<[8..=16:8], lanes, clks>);
+                self.input => check.input;
+                check.output => range_check.input;
+                range_check.output => self.output;
+                assert(widths[1] == 16 && doubled[1] == 32 && bit_width(lanes[1]) == 4, "variable");
+            }
+            impl wrong_element_i of s {
+                mixed: [int] = [1, "2"];
+                assert(mixed[0] == 1);
+            }
+            impl not_array_i of s {
+                single: [int] = 3;
+                assert(single == 3);
+            }
+            impl wrong_logic_type_i of s {
+                types: [type] = [Bit(8), 1];
+                assert(bit_width(types[0]) == 8);
+            }
+            impl wrong_template_arg_i of s {
+                instance check(check_i<widths, lanes, [1, 2]>);
+            }
+            impl no_value_i of s {
+                xs: [int];
+                assert(len(xs) == 0);
+            }
+            "#);
+
+            let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        project.read().unwrap().evaluate_target(format!("top_i"), format!("pack0")).expect("fail to evaluate");
+
+        check_evaluation_error(&project, "pack0", "wrong_element_i", "element 1 of variable mixed should be int, get String(2)");
+        check_evaluation_error(&project, "pack0", "not_array_i", "variable single is declared as an array of int, get Int(3)");
+        check_evaluation_error(&project, "pack0", "wrong_logic_type_i", "element 1 of variable types should be any_logic_type, get Int(1)");
+        check_evaluation_error(&project, "pack0", "wrong_template_arg_i", "template argument index 2, expected [clock_domain], get Array(Int(1), Int(2))");
+        check_evaluation_error(&project, "pack0", "no_value_i", "typed array variable xs has no value");
+    }
+
+    #[test]
+    fn sample_project_streamlet_extends_0() {
+        let project = Project::new(format!("sample_project"));
+        {
+            let mut project_write = project.write().unwrap();
+
+            let src_pack0 = String::from(r#"
+            package pack0;
+
+            streamlet base_s<T: type> {
+                width = bit_width(T);
+                input: Stream(T) in;
+                output: Stream(T) out;
+            }
+            streamlet debug_s<T: type> extends base_s<T> {
+                debug: Stream(Bit(width)) in;
+            }
+            impl base_i<T: type> of base_s<T> {
+                self.input => self.output;
+            }
+            impl debug_i<T: type> of debug_s<T> {
+                instance inner(base_i<T>);
+                self.input => inner.input;
+                inner.output => self.output;
+            }
+            impl top_i of debug_s<Bit(8)> {
+                instance dbg(debug_i<Bit(8)>);
+                self.input => dbg.input;
+                self.debug => dbg.debug;
+                dbg.output => self.output;
+            }
+
+            streamlet plain_s {
+                input: Stream(Bit(4)) in;
+                output: Stream(Bit(4)) out;
+            }
+            streamlet plain_debug_s extends plain_s {
+                debug: Stream(Bit(1)) in;
+            }
+            impl plain_debug_i of plain_debug_s {
+                self.input => self.output;
+            }
+            impl wrapper_i<inner_impl: impl of plain_s> of plain_s {
+                instance inner(inner_impl);
+                self.input => inner.input;
+                inner.output => self.output;
+            }
+            impl wrapped_debug_i of plain_s {
+                instance wrapper(wrapper_i<plain_debug_i>);
+                self.input => wrapper.input;
+                wrapper.output => self.output;
+            }
+            impl plain_i of plain_s {
+                self.input => self.output;
+            }
+            impl wrapped_plain_i of plain_s {
+                instance wrapper(wrapper_i<plain_i>);
+                self.input => wrapper.input;
+                wrapper.output => self.output;
+            }
+            streamlet unrelated_s {
+                input: Stream(Bit(4)) in;
+                output: Stream(Bit(4)) out;
+            }
+            impl unrelated_i of unrelated_s {
+                self.input => self.output;
+            }
+            impl wrapped_unrelated_i of plain_s {
+                instance wrapper(wrapper_i<unrelated_i>);
+                self.input => wrapper.input;
+                wrapper.output => self.output;
+            }
+            streamlet conflict_s extends plain_s {
+                input: Stream(Bit(8)) in;
+            }
+            impl conflict_i of conflict_s {
+                self.input => self.output @NoTypeCheck;
+            }
+            streamlet not_streamlet_s extends plain_debug_i {
+                debug: Stream(Bit(1)) in;
+            }
+            impl not_streamlet_i of not_streamlet_s {
+            }
+            "#);
+
+            let status = project_write.add_package(format!("./pack0.td"), src_pack0);
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        project.read().unwrap().evaluate_target(format!("top_i"), format!("pack0")).expect("fail to evaluate");
+        project.read().unwrap().evaluate_target(format!("plain_debug_i"), format!("pack0")).expect("fail to evaluate");
+        //an implementation of the extending streamlet can be used where an implementation of the extended streamlet is expected
+        project.read().unwrap().evaluate_target(format!("wrapped_debug_i"), format!("pack0")).expect("fail to evaluate");
+        project.read().unwrap().evaluate_target(format!("wrapped_plain_i"), format!("pack0")).expect("fail to evaluate");
+        {
+            let package = project.read().unwrap().get_packages()[&format!("pack0")].clone();
+            let plain_debug_s = package.read().unwrap().get_scope().read().unwrap().get_variables()[&format!("plain_debug_s")].clone();
+            let plain_debug_s = plain_debug_s.read().unwrap().get_value();
+            match plain_debug_s {
+                TypedValue::Streamlet(streamlet) => {
+                    let ports = streamlet.read().unwrap().get_all_ports();
+                    assert!(ports.len() == 3, "inherited ports");
+                    assert!(ports.iter().all(|port| port.read().unwrap().get_parent_streamlet().unwrap().read().unwrap().get_brief_info().starts_with("Streamlet(plain_debug_s)")), "parent of inherited ports");
+                    assert!(ports.iter().all(|port| port.read().unwrap().get_parent_scope().unwrap().read().unwrap().get_name() == "streamlet_plain_debug_s"), "parent scope of inherited ports");
+                },
+                v => panic!("plain_debug_s should be a streamlet, found {}", v.get_brief_info()),
+            }
+
+            let wrapped_debug_i = package.read().unwrap().get_scope().read().unwrap().get_variables()[&format!("wrapped_debug_i")].clone();
+            let wrapped_debug_i = wrapped_debug_i.read().unwrap().get_value();
+            match wrapped_debug_i {
+                TypedValue::Implementation(implementation) => {
+                    let wrapper = implementation.read().unwrap().get_all_instances().into_iter().find(|inst| inst.read().unwrap().get_name() == "wrapper").expect("wrapper instance");
+                    let wrapper_impl = wrapper.read().unwrap().get_derived_impl().unwrap();
+                    let nets = wrapper_impl.read().unwrap().get_all_nets();
+                    assert!(nets.len() == 2, "nets of the wrapper, get {}", nets.len());
+                    let inner_ports: Vec<String> = nets.iter().map(|net| {
+                        let net_read = net.read().unwrap();
+                        let inner_port = match net_read.get_source_port_owner() {
+                            PortOwner::ImplInstance(_) => net_read.get_source_port().unwrap(),
+                            _ => net_read.get_sink_port().unwrap(),
+                        };
+                        let parent_streamlet = inner_port.read().unwrap().get_parent_streamlet().unwrap().read().unwrap().get_name();
+                        let port_name = inner_port.read().unwrap().get_id_in_scope().unwrap();
+                        format!("{}.{}", parent_streamlet, port_name)
+                    }).collect();
+                    assert!(inner_ports.contains(&format!("plain_debug_s.input")) && inner_ports.contains(&format!("plain_debug_s.output")), "the wrapper connects the inherited ports, get {:?}", inner_ports);
+                },
+                v => panic!("wrapped_debug_i should be an implementation, found {}", v.get_brief_info()),
+            }
+        }
+
+        let e = check_evaluation_error(&project, "pack0", "conflict_i", "port input of streamlet conflict_s conflicts with the port inherited from streamlet plain_s");
+        assert!(e.location.len() == 2, "{:?}", e.location);
+        check_evaluation_error(&project, "pack0", "not_streamlet_i", "is not a streamlet, but streamlet(not_streamlet_s) extends it");
+        //an implementation of a streamlet with the same ports is rejected if the streamlet doesn't extend the expected one
+        check_evaluation_error(&project, "pack0", "wrapped_unrelated_i", "template argument inner_impl of variable wrapper_i must be an implementation of plain_s or of a streamlet extending it");
+    }
+
+    #[test]
+    fn sample_project_port_bundle_0() {
+        let project = Project::new(format!("sample_project"));
+        {
+            let status = project.write().unwrap().add_package(format!("./pack0.td"), String::from(r#"
+            package pack0;
+
+            interface req_resp<W: int> {
+                req: Stream(Bit(W)) out;
+                resp: Stream(Bit(8)) in;
+            }
+
+            interface req_resp_16 {
+                req: Stream(Bit(16)) out;
+                resp: Stream(Bit(8)) in;
+            }
+
+            streamlet master_s {
+                m: bundle req_resp<16>;
+            }
+            impl master_i of master_s {}
+
+            streamlet slave_s {
+                s: flip bundle req_resp<16>;
+            }
+            impl slave_i of slave_s {}
+
+            streamlet top_s {
+                up: flip bundle req_resp<16>;
+                for i in [0..2] {
+                    ch: bundle req_resp_16;
+                }
+            }
+            impl top_i of top_s {
+                instance master(master_i);
+                instance slave(slave_i);
+                master.m => slave.s;
+                self.up => slave.s;
+                master.m => self.ch[1];
+            }
+            impl bundle_in_for_i of top_s {
+                for i in [0..2] {
+                    instance master(master_i);
+                    instance slave(slave_i);
+                    master.m => slave.s;
+                }
+            }
+
+            // errors
+            streamlet not_interface_s {
+                b: bundle master_s;
+            }
+            impl not_interface_i of not_interface_s {}
+
+            impl interface_i of req_resp<16> {}
+
+            interface other<W: int> {
+                req: Stream(Bit(W)) out;
+            }
+            streamlet other_s {
+                o: flip bundle other<16>;
+            }
+            impl other_i of other_s {}
+            impl mismatch_i of top_s {
+                instance master(master_i);
+                instance o(other_i);
+                master.m => o.o;
+            }
+            impl bundle_to_port_i of top_s {
+                instance master(master_i);
+                master.m => self.up_req;
+            }
+            impl direction_i of top_s {
+                instance master(master_i);
+                instance master2(master_i);
+                master.m => master2.m;
+            }
+            "#));
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        let result = project.read().unwrap().evaluate_target(format!("top_i"), format!("pack0"));
+        if let Err(e) = result {
+            panic!("{}", e.print());
+        }
+        {
+            let package = project.read().unwrap().get_packages()[&format!("pack0")].clone();
+            let slave_s = package.read().unwrap().get_scope().read().unwrap().get_variables()[&format!("slave_s")].clone();
+            let slave_s = slave_s.read().unwrap().get_value();
+            match slave_s {
+                TypedValue::Streamlet(streamlet) => {
+                    let brief_info = streamlet.read().unwrap().get_brief_info();
+                    assert!(brief_info.contains("s_req:In") && brief_info.contains("s_resp:Out"), "{}", brief_info);
+                    let ports = streamlet.read().unwrap().get_all_ports();
+                    assert!(ports.len() == 2, "expanded ports of slave_s");
+                    assert!(ports.iter().all(|port| port.read().unwrap().get_parent_scope().unwrap().read().unwrap().get_name() == "streamlet_slave_s"), "parent scope of expanded ports");
+                },
+                v => panic!("slave_s should be a streamlet, found {}", v.get_brief_info()),
+            }
+
+            let top_i = package.read().unwrap().get_scope().read().unwrap().get_variables()[&format!("top_i")].clone();
+            let top_i = top_i.read().unwrap().get_value();
+            match top_i {
+                TypedValue::Implementation(implementation) => {
+                    let nets = implementation.read().unwrap().get_all_nets();
+                    assert!(nets.len() == 6, "each bundle net is expanded into 2 nets, get {}", nets.len());
+                    let net_ports: Vec<String> = nets.iter().map(|net| {
+                        let net_read = net.read().unwrap();
+                        format!("{}=>{}", net_read.get_source_port().unwrap().read().unwrap().get_id_in_scope().unwrap(), net_read.get_sink_port().unwrap().read().unwrap().get_id_in_scope().unwrap())
+                    }).collect();
+                    for expected in ["m_req=>s_req", "s_resp=>m_resp", "up_req=>s_req", "s_resp=>up_resp", "m_req=>ch_req_for1", "ch_resp_for1=>m_resp"] {
+                        assert!(net_ports.contains(&format!("{}", expected)), "missing net {}, get {:?}", expected, net_ports);
+                    }
+                },
+                v => panic!("top_i should be an implementation, found {}", v.get_brief_info()),
+            }
+        }
+        let json_output = project.read().unwrap().get_pretty_json();
+        assert!(json_output.contains("\"up_req\"") && json_output.contains("\"ch_resp_for1\""), "bundle ports in json");
+
+        //bundle nets in "for" are expanded in each iteration
+        project.read().unwrap().evaluate_target(format!("bundle_in_for_i"), format!("pack0")).expect("fail to evaluate bundle_in_for_i");
+        {
+            let package = project.read().unwrap().get_packages()[&format!("pack0")].clone();
+            let bundle_in_for_i = package.read().unwrap().get_scope().read().unwrap().get_variables()[&format!("bundle_in_for_i")].clone();
+            let bundle_in_for_i = bundle_in_for_i.read().unwrap().get_value();
+            match bundle_in_for_i {
+                TypedValue::Implementation(implementation) => {
+                    let nets = implementation.read().unwrap().get_all_nets();
+                    let net_ports: Vec<String> = nets.iter().map(|net| {
+                        let net_read = net.read().unwrap();
+                        format!("{}=>{}", net_read.get_source_port().unwrap().read().unwrap().get_id_in_scope().unwrap(), net_read.get_sink_port().unwrap().read().unwrap().get_id_in_scope().unwrap())
+                    }).collect();
+                    assert!(nets.len() == 4, "each iteration expands a bundle net into 2 nets, get {:?}", net_ports);
+                    assert!(net_ports.iter().filter(|net| *net == "m_req=>s_req").count() == 2 && net_ports.iter().filter(|net| *net == "s_resp=>m_resp").count() == 2, "get {:?}", net_ports);
+                },
+                v => panic!("bundle_in_for_i should be an implementation, found {}", v.get_brief_info()),
+            }
+        }
+
+        check_evaluation_error(&project, "pack0", "not_interface_i", "Streamlet(master_s){m_req:Out");
+        check_evaluation_error(&project, "pack0", "not_interface_i", "is not an interface, but port bundle b uses it");
+        check_evaluation_error(&project, "pack0", "interface_i", "is an interface, it can only be used as a port bundle, but used in defining impl(interface_i)");
+        check_evaluation_error(&project, "pack0", "mismatch_i", "port resp of bundle m has no counterpart in bundle o");
+        check_evaluation_error(&project, "pack0", "bundle_to_port_i", "a port bundle can only be connected to another port bundle");
+        check_evaluation_error(&project, "pack0", "direction_i", "net (master.m => master2.m) uses m_req as sink, but it cannot receive from a net (Out port of instance master2)");
+    }
+
+    #[test]
+    fn sample_project_bulk_net_0() {
+        let project = Project::new(format!("sample_project"));
+        {
+            let status = project.write().unwrap().add_package(format!("./pack0.td"), String::from(r#"
+            package pack0;
+
+            streamlet stage_s {
+                data_in: Stream(Bit(8)) in;
+                last_in: Stream(Bit(1)) in;
+                for i in [0, 1] {
+                    lanes_in: Stream(Bit(4)) in;
+                }
+                data_out: Stream(Bit(8)) out;
+                last_out: Stream(Bit(1)) out;
+                for i in [0, 1] {
+                    lanes_out: Stream(Bit(4)) out;
+                }
+            }
+            impl stage_i of stage_s {}
+
+            streamlet consumer_s {
+                data_out: Stream(Bit(8)) in;
+                last_out: Stream(Bit(1)) in;
+                for i in [0, 1] {
+                    lanes_out: Stream(Bit(4)) in;
+                }
+            }
+            impl consumer_i of consumer_s {}
+
+            streamlet top_s {
+                data_in: Stream(Bit(8)) in;
+                last_in: Stream(Bit(1)) in;
+                for i in [0, 1] {
+                    lanes_in: Stream(Bit(4)) in;
+                }
+                data_out: Stream(Bit(8)) out;
+                last_out: Stream(Bit(1)) out;
+                for i in [0, 1] {
+                    lanes_out: Stream(Bit(4)) out;
+                }
+            }
+            impl top_i of top_s {
+                instance s0(stage_i);
+                instance s1(stage_i);
+                instance c(consumer_i);
+                self.* => s0.*;
+                s0.* => c . *;
+                s1.* => self.*;
+            }
+            impl bulk_in_for_i of top_s {
+                for i in [0, 1] {
+                    instance s(stage_i);
+                    instance c(consumer_i);
+                    s.* => c.*;
+                }
+            }
+
+            // errors
+            streamlet narrow_s {
+                data_out: Stream(Bit(8)) in;
+                extra: Stream(Bit(8)) in;
+            }
+            impl narrow_i of narrow_s {}
+            impl unmatched_i of top_s {
+                instance s0(stage_i);
+                instance n(narrow_i);
+                s0.* => n.*;
+            }
+            streamlet wide_s {
+                data_out: Stream(Bit(16)) in;
+                last_out: Stream(Bit(1)) in;
+                for i in [0, 1] {
+                    lanes_out: Stream(Bit(4)) in;
+                }
+            }
+            impl wide_i of wide_s {}
+            impl type_mismatch_i of top_s {
+                instance s0(stage_i);
+                instance w(wide_i);
+                s0.* => w.*;
+            }
+            streamlet single_lane_s {
+                data_out: Stream(Bit(8)) in;
+                last_out: Stream(Bit(1)) in;
+                lanes_out: Stream(Bit(4)) in;
+            }
+            impl single_lane_i of single_lane_s {}
+            impl array_mismatch_i of top_s {
+                instance s0(stage_i);
+                instance l(single_lane_i);
+                s0.* => l.*;
+            }
+            impl not_instance_i of top_s {
+                instance s0(stage_i);
+                lanes = 2;
+                s0.* => lanes.*;
+            }
+            streamlet producer_s {
+                data_out: Stream(Bit(8)) out;
+            }
+            impl producer_i of producer_s {}
+            impl no_port_i of top_s {
+                instance c(consumer_i);
+                instance p(producer_i);
+                c.* => p.*;
+            }
+            "#));
+            if status.is_err() {
+                panic!("{}", status.err().unwrap().print());
+            }
+        }
+        let result = project.read().unwrap().evaluate_target(format!("top_i"), format!("pack0"));
+        if let Err(e) = result {
+            panic!("{}", e.print());
+        }
+        {
+            let package = project.read().unwrap().get_packages()[&format!("pack0")].clone();
+            let top_i = package.read().unwrap().get_scope().read().unwrap().get_variables()[&format!("top_i")].clone();
+            let top_i = top_i.read().unwrap().get_value();
+            match top_i {
+                TypedValue::Implementation(implementation) => {
+                    let nets = implementation.read().unwrap().get_all_nets();
+                    assert!(nets.len() == 12, "each bulk net is expanded into 4 nets, get {}", nets.len());
+                    let net_ports: Vec<String> = nets.iter().map(|net| {
+                        let net_read = net.read().unwrap();
+                        let source_owner = match net_read.get_source_port_owner() {
+                            PortOwner::ImplSelf => format!("self"),
+                            PortOwner::ImplInstance(inst) => inst.read().unwrap().get_name(),
+                            PortOwner::Unknown => format!("???"),
+                        };
+                        let sink_owner = match net_read.get_sink_port_owner() {
+                            PortOwner::ImplSelf => format!("self"),
+                            PortOwner::ImplInstance(inst) => inst.read().unwrap().get_name(),
+                            PortOwner::Unknown => format!("???"),
+                        };
+                        format!("{}.{}=>{}.{}", source_owner, net_read.get_source_port().unwrap().read().unwrap().get_id_in_scope().unwrap(), sink_owner, net_read.get_sink_port().unwrap().read().unwrap().get_id_in_scope().unwrap())
+                    }).collect();
+                    for expected in ["self.data_in=>s0.data_in", "self.last_in=>s0.last_in", "s0.data_out=>c.data_out", "s1.last_out=>self.last_out", "s1.lanes_out_for1=>self.lanes_out_for1"] {
+                        assert!(net_ports.contains(&format!("{}", expected)), "missing net {}, get {:?}", expected, net_ports);
+                    }
+                    assert!(net_ports.contains(&format!("self.lanes_in_for0=>s0.lanes_in_for0")) && net_ports.contains(&format!("self.lanes_in_for1=>s0.lanes_in_for1")), "port arrays are connected element by element, get {:?}", net_ports);
+                },
+                v => panic!("top_i should be an implementation, found {}", v.get_brief_info()),
+            }
+        }
+
+        //bulk nets in "for" are expanded in each iteration
+        project.read().unwrap().evaluate_target(format!("bulk_in_for_i"), format!("pack0")).expect("fail to evaluate bulk_in_for_i");
+        {
+            let package = project.read().unwrap().get_packages()[&format!("pack0")].clone();
+            let bulk_in_for_i = package.read().unwrap().get_scope().read().unwrap().get_variables()[&format!("bulk_in_for_i")].clone();
+            let bulk_in_for_i = bulk_in_for_i.read().unwrap().get_value();
+            match bulk_in_for_i {
+                TypedValue::Implementation(implementation) => {
+                    let nets = implementation.read().unwrap().get_all_nets();
+                    assert!(nets.len() == 8, "each iteration expands a bulk net into 4 nets, get {}", nets.len());
+                },
+                v => panic!("bulk_in_for_i should be an implementation, found {}", v.get_brief_info()),
+            }
+        }
+
+        check_evaluation_error(&project, "pack0", "unmatched_i", "bulk net (s0.* => n.*) has unmatched ports: s0.lanes_out (no same-named port to receive from it), s0.last_out (no same-named port to receive from it), n.extra (no same-named port to drive it)");
+        check_evaluation_error(&project, "pack0", "type_mismatch_i", "net (s0.data_out => w.data_out) connects incompatible logic types");
+        check_evaluation_error(&project, "pack0", "array_mismatch_i", "cannot connect s0.lanes_out to l.lanes_out, they must be both ports or both port arrays of the same size");
+        check_evaluation_error(&project, "pack0", "not_instance_i", "lanes is not an instance, but used in bulk net (s0.* => lanes.*)");
+        check_evaluation_error(&project, "pack0", "no_port_i", "bulk net (c.* => p.*) connects no ports");
+    }
+
 }
@@ -109,6 +109,9 @@ mod test {
         try_parse(String::from("bitwise(1,10)"), Rule::Exp, true).ok().unwrap();
         try_parse(String::from("1>>8"), Rule::Exp, true).ok().unwrap();
         try_parse(String::from("1<<8"), Rule::Exp, true).ok().unwrap();
+        try_parse(String::from("dup<T, N=4>"), Rule::Exp, true).ok().unwrap();
+        try_parse(String::from("dup<N = 4, T = Bit(8)>.x"), Rule::Exp, true).ok().unwrap();
+        try_parse(String::from("dup<a == b>"), Rule::Exp, true).ok().unwrap();
         try_parse(String::from("true || false"), Rule::Exp, true).ok().unwrap();
         try_parse(String::from("true && false"), Rule::Exp, true).ok().unwrap();
         try_parse(String::from("x.y"), Rule::Exp, true).ok().unwrap();
@@ -123,6 +126,27 @@ mod test {
         try_parse(String::from("x&y"), Rule::Exp, true).ok().unwrap();
         try_parse(String::from("10/5"), Rule::Exp, true).ok().unwrap();
         try_parse(String::from("(1+2)/3*x[4]/8"), Rule::Exp, true).ok().unwrap();
+        try_parse(String::from("int(\"8\") + float(x) * int(2.5, \"round\")"), Rule::Exp, true).ok().unwrap();
+        try_parse(String::from("bool(x) || integer(1)"), Rule::Exp, true).ok().unwrap();
+        try_parse(String::from("int"), Rule::Exp, true).err().unwrap();
+        try_parse(String::from("x > 1 ? 2 : 3"), Rule::Exp, true).ok().unwrap();
+        try_parse(String::from("a ? b ? 1 : 2 : c ? 3 : 4"), Rule::Exp, true).ok().unwrap();
+        try_parse(String::from("if x > 1 then 2 else 3"), Rule::Exp, true).ok().unwrap();
+        try_parse(String::from("if(x)then(1)else if y then 2 else 3"), Rule::Exp, true).ok().unwrap();
+        try_parse(String::from("wide ? Bit(64) : Bit(32)"), Rule::Exp, true).ok().unwrap();
+        try_parse(String::from("ifx + thenx"), Rule::Exp, true).ok().unwrap();
+        try_parse(String::from("if x then 1"), Rule::Exp, true).err().unwrap();
+        try_parse(String::from("x ? 1"), Rule::Exp, true).err().unwrap();
+        try_parse(String::from("[i * 2 for i in range(4)]"), Rule::Exp, true).ok().unwrap();
+        try_parse(String::from("[Bit(i) for i in [1..4] if i % 2 == 0]"), Rule::Exp, true).ok().unwrap();
+        try_parse(String::from("[[j for j in range(i)] for i in x]"), Rule::Exp, true).ok().unwrap();
+        try_parse(String::from("[format_i for in_x in inputs]"), Rule::Exp, true).ok().unwrap();
+        try_parse(String::from("[i for i]"), Rule::Exp, true).err().unwrap();
+        try_parse(String::from("[i for in x]"), Rule::Exp, true).err().unwrap();
+        try_parse(String::from("{width: 32, \"depth\": 1024, latency: x + 1}"), Rule::Exp, true).ok().unwrap();
+        try_parse(String::from("{a: {b: [1, 2]},}"), Rule::Exp, true).ok().unwrap();
+        try_parse(String::from("{}"), Rule::Exp, true).err().unwrap();
+        try_parse(String::from("{1: 2}"), Rule::Exp, true).err().unwrap();
     }
 
     #[test]
@@ -170,6 +194,18 @@ mod test {
         }
         "), Rule::LogicalType, false).ok().unwrap();
         try_parse(String::from("\
+        Group x <T: type, N: int = 1, M: int = (N * 2), U: type = Bit(8)> {
+            x : T;
+            y : Bit(M);
+        }
+        "), Rule::LogicalType, false).ok().unwrap();
+        try_parse(String::from("\
+        Group x <T: type is Stream, N: int where N > 0 && N <= 64, M: int where M % 2 == 0 = 2> {
+            x : T;
+            y : Bit(M);
+        }
+        "), Rule::LogicalType, false).ok().unwrap();
+        try_parse(String::from("\
         Union x <v0: int, v1: int> {
             value: int = 1;
             string0 = \"123\";
@@ -217,6 +253,23 @@ mod test {
             port_out : Stream(Bit(8)) out [b] /clock_domain @any_clockDomain @NoTypeCheck;
         }
         "), Rule::TydiFile, false).ok().unwrap();
+        try_parse(String::from("\
+        package test;
+        streamlet y<len: int> extends external_package.x<len, i> @attribute {
+            debug : Stream(Bit(1)) out;
+        }
+        "), Rule::TydiFile, false).ok().unwrap();
+        try_parse(String::from("\
+        package test;
+        interface x<len: int> {
+            req : Stream(Bit(len)) out;
+            resp : Stream(Bit(8)) in;
+        }
+        streamlet y {
+            m : bundle x<8>;
+            s : flip bundle external_package.x<8> @NoTypeCheck;
+        }
+        "), Rule::TydiFile, false).ok().unwrap();
     }
 
     #[test]
@@ -247,6 +300,12 @@ mod test {
         package test;
         impl x_impl<x:int, y:type> of external_package.x <1,Bit(1)> @attribute {
             
+        }
+        "), Rule::TydiFile, false).ok().unwrap();
+        try_parse(String::from("\
+        package test;
+        impl x_impl<x:int where x > 0, y:type is Group> of external_package.x <x,y> {
+            
         }
         "), Rule::TydiFile, false).ok().unwrap();
         try_parse(String::from("\
@@ -269,6 +328,16 @@ mod test {
             i1.out_p => i2.in_p \"net_name\" @NoTypeCheck @SecondAttr;
         }
         "), Rule::TydiFile, false).ok().unwrap();
+        try_parse(String::from("\
+        package test;
+        impl x_impl of y {
+            instance i0(impl0);
+            instance i1(impl0);
+            self.* => i0.*;
+            i0 . * => i1.* \"net_name\" @NoTypeCheck;
+            i1.* => self.*;
+        }
+        "), Rule::TydiFile, false).ok().unwrap();
     }
 
     #[test]
@@ -11,6 +11,7 @@ mod parse_type;
 #[allow(unused_imports)]
 use parse_type::*;
 pub (in crate) use parse_type::parse_LogicalType_Basic;
+pub (in crate) use parse_type::parse_LogicalType_Array;
 
 
 mod parse_logic_type;
@@ -37,6 +38,10 @@ mod parse_logic_flow;
 #[allow(unused_imports)]
 use parse_logic_flow::*;
 
+mod parse_function;
+#[allow(unused_imports)]
+use parse_function::*;
+
 
 use crate::error::TydiLangError;
 use crate::generate_name::generate_init_value;
@@ -38,9 +38,15 @@ pub fn parse_Scope_WithoutBracket(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, ra
             Rule::StatementDeclareStreamlet => {
                 parse_StatementDeclareStreamlet(element, scope.clone(), raw_src.clone())?;
             }
+            Rule::StatementDeclareInterface => {
+                parse_StatementDeclareInterface(element, scope.clone(), raw_src.clone())?;
+            }
             Rule::StatementDeclarePort => {
                 parse_StatementDeclarePort(element, scope.clone(), raw_src.clone())?;
             }
+            Rule::StatementDeclarePortBundle => {
+                parse_StatementDeclarePortBundle(element, scope.clone(), raw_src.clone())?;
+            }
             Rule::StatementDeclareImplementation => {
                 parse_StatementDeclareImplementation(element, scope.clone(), raw_src.clone())?;
             }
@@ -62,6 +68,9 @@ pub fn parse_Scope_WithoutBracket(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, ra
             Rule::StatementFunction => {
                 parse_StatementFunction(element, scope.clone(), raw_src.clone())?;
             }
+            Rule::StatementDeclareFunction => {
+                parse_StatementDeclareFunction(element, scope.clone(), raw_src.clone())?;
+            }
             _ => unreachable!()
         }
     }
@@ -0,0 +1,119 @@
+use std::sync::{Arc, RwLock};
+use std::collections::BTreeMap;
+
+use crate::error::TydiLangError;
+use crate::generate_name::{generate_init_value, generate_built_in_variable_name_from_span};
+use crate::trait_common::{GetName, HasDocument};
+use crate::tydi_memory_representation::{Scope, GetScope, Variable, TraitCodeLocationAccess, CodeLocation, FunctionDefinition, TemplateArg, TypedValue, SrcInfo};
+use crate::tydi_parser::*;
+
+use crate::tydi_lang_src_to_memory_representation::{parse_template, parse_type, parse_file};
+
+#[allow(non_snake_case)]
+pub fn parse_FunctionDefinition(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcInfo>) -> Result<Arc<RwLock<Variable>>, TydiLangError> {
+    let mut output_function = FunctionDefinition::new(generate_init_value(), scope.clone());
+    let mut document = None;
+    let mut name = generate_init_value();
+    let mut args = BTreeMap::new();
+    let mut return_type = None;
+
+    for element in src.clone().into_inner().into_iter() {
+        let rule = element.as_rule();
+        match rule {
+            Rule::DOCUMENT_CONTENT => {
+                document = Some(element.as_str().to_string());
+            }
+            Rule::ID => {
+                name = element.as_str().to_string();
+                output_function = FunctionDefinition::new(name.clone(), scope.clone());
+            }
+            Rule::FunctionArgs => {
+                args = parse_FunctionArgs(element, scope.clone(), raw_src.clone())?;
+            }
+            Rule::FunctionReturnType => {
+                return_type = Some(parse_FunctionReturnType(element, scope.clone(), raw_src.clone())?);
+            }
+            Rule::FunctionBody_Exp | Rule::FunctionBody_Scope => {
+                parse_FunctionBody(element, output_function.clone(), raw_src.clone())?;
+            }
+            _ => unreachable!()
+        }
+    }
+    {
+        let mut output_function_write = output_function.write().unwrap();
+        output_function_write.set_args(args);
+        output_function_write.set_return_type(return_type);
+        output_function_write.set_document(document);
+        output_function_write.set_code_location(CodeLocation::new_from_pest_rule(&src, raw_src.clone()));
+    }
+
+    let output_function_var = Variable::new_predefined(name.clone(), TypedValue::FunctionDefinition(output_function));
+    {
+        let mut output_function_var_write = output_function_var.write().unwrap();
+        output_function_var_write.set_code_location(CodeLocation::new_from_pest_rule(&src, raw_src.clone()));
+        output_function_var_write.set_is_name_user_defined(true);
+    }
+    return Ok(output_function_var);
+}
+
+#[allow(non_snake_case)]
+pub fn parse_FunctionArgs(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcInfo>) -> Result<BTreeMap<usize, TemplateArg>, TydiLangError> {
+    let mut arg_container: BTreeMap<usize, TemplateArg> = BTreeMap::new();
+    for element in src.clone().into_inner().into_iter() {
+        let rule = element.as_rule();
+        match rule {
+            Rule::Arg => {
+                let arg = parse_template::parse_Arg(element, scope.clone(), raw_src.clone())?;
+                for (_, existing_arg) in &arg_container {
+                    if existing_arg.get_name() == arg.get_name() {
+                        return Err(TydiLangError::new_multiple_locations(format!("function argument {} redefined", arg.get_name()), vec![existing_arg.get_code_location(), arg.get_code_location()]));
+                    }
+                }
+                arg_container.insert(arg_container.len(), arg);
+            }
+            _ => unreachable!()
+        }
+    }
+    return Ok(arg_container);
+}
+
+#[allow(non_snake_case)]
+pub fn parse_FunctionReturnType(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcInfo>) -> Result<TemplateArg, TydiLangError> {
+    let mut output_type = TemplateArg::new_place_holder();
+    for element in src.clone().into_inner().into_iter() {
+        let rule = element.as_rule();
+        match rule {
+            Rule::AllTypeKeyword => {
+                let (type_indication, is_array) = parse_type::parse_AllTypeKeyword(element, scope.clone(), raw_src.clone())?;
+                output_type.set_is_array(is_array);
+                output_type.set_type_indication(type_indication);
+            }
+            _ => unreachable!()
+        }
+    }
+    output_type.set_code_location(CodeLocation::new_from_pest_rule(&src, raw_src.clone()));
+    return Ok(output_type);
+}
+
+#[allow(non_snake_case)]
+pub fn parse_FunctionBody(src: Pair<Rule>, function: Arc<RwLock<FunctionDefinition>>, raw_src: Arc<SrcInfo>) -> Result<(), TydiLangError> {
+    for element in src.clone().into_inner().into_iter() {
+        let rule = element.as_rule();
+        match rule {
+            Rule::FunctionScope => {
+                let function_scope = function.read().unwrap().get_scope();
+                parse_file::parse_Scope_WithoutBracket(element, function_scope.clone(), raw_src.clone())?;
+            }
+            Rule::Exp => {
+                let return_var = Variable::new(generate_built_in_variable_name_from_span(&element, &raw_src), Some(element.as_str().to_string()));
+                {
+                    let mut return_var_write = return_var.write().unwrap();
+                    return_var_write.set_code_location(CodeLocation::new_from_pest_rule(&element, raw_src.clone()));
+                }
+                function.write().unwrap().set_return_exp(return_var);
+            }
+            _ => unreachable!()
+        }
+    }
+    return Ok(());
+}
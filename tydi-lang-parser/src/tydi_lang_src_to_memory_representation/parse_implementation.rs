@@ -133,11 +133,14 @@ pub fn parse_Instance(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<S
 #[allow(non_snake_case)]
 pub fn parse_Net(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcInfo>) -> Result<Arc<RwLock<Variable>>, TydiLangError> {
     let mut document = None;
-    let name = generate_built_in_variable_name_from_span(&src);
+    let name = generate_built_in_variable_name_from_span(&src, &raw_src);
     let mut net_name = None;
     let mut attributes = vec![];
     let mut source_var = Variable::new_place_holder();
     let mut sink_var = Variable::new_place_holder();
+    //a bulk net connects port owners (instances or self) instead of ports
+    let is_bulk = src.as_rule() == Rule::NetBulk;
+    let end_type_indication = if is_bulk { TypeIndication::Any } else { TypeIndication::AnyPort };
 
     let mut exp_index = 0;
     for element in src.clone().into_inner().into_iter() {
@@ -148,13 +151,13 @@ pub fn parse_Net(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcInf
             }
             Rule::Exp => {
                 if exp_index == 0 {
-                    source_var = Variable::new_with_type_indication(generate_built_in_variable_name_from_span(&element), Some(element.as_str().to_string().replace(" ", "")), TypeIndication::AnyPort);
+                    source_var = Variable::new_with_type_indication(generate_built_in_variable_name_from_span(&element, &raw_src), Some(element.as_str().to_string().replace(" ", "")), end_type_indication.clone());
                     {
                         let mut source_var_write = source_var.write().unwrap();
                         source_var_write.set_code_location(CodeLocation::new_from_pest_rule(&element, raw_src.clone()));
                     }
                 } else if exp_index == 1 {
-                    sink_var = Variable::new_with_type_indication(generate_built_in_variable_name_from_span(&element), Some(element.as_str().to_string().replace(" ", "")), TypeIndication::AnyPort);
+                    sink_var = Variable::new_with_type_indication(generate_built_in_variable_name_from_span(&element, &raw_src), Some(element.as_str().to_string().replace(" ", "")), end_type_indication.clone());
                     {
                         let mut sink_var_write = sink_var.write().unwrap();
                         sink_var_write.set_code_location(CodeLocation::new_from_pest_rule(&element, raw_src.clone()));
@@ -185,6 +188,7 @@ pub fn parse_Net(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcInf
         output_net_write.set_source(source_var);
         output_net_write.set_sink(sink_var);
         output_net_write.set_net_name(net_name);
+        output_net_write.set_is_bulk(is_bulk);
         output_net_write.set_document(document);
         output_net_write.set_attributes(attributes);
         output_net_write.set_code_location(CodeLocation::new_from_pest_rule(&src, raw_src.clone()));
@@ -204,7 +208,7 @@ pub fn parse_Netname(src: Pair<Rule>, _scope: Arc<RwLock<Scope>>, raw_src: Arc<S
         let rule = element.as_rule();
         match rule {
             Rule::Exp => {
-                let output_var = Variable::new_with_type_indication(generate_built_in_variable_name_from_span(&element), Some(element.as_str().to_string()), TypeIndication::String);
+                let output_var = Variable::new_with_type_indication(generate_built_in_variable_name_from_span(&element, &raw_src), Some(element.as_str().to_string()), TypeIndication::String);
                 {
                     let mut output_var_write = output_var.write().unwrap();
                     output_var_write.set_code_location(CodeLocation::new_from_pest_rule(&element, raw_src.clone()));
@@ -10,12 +10,12 @@ use crate::tydi_lang_src_to_memory_representation::{parse_file};
 
 #[allow(non_snake_case)]
 pub fn parse_If(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcInfo>) -> Result<Arc<RwLock<Variable>>, TydiLangError> {
-    let output_if = If::new(generate_built_in_variable_name_from_span(&src), scope.clone());
+    let output_if = If::new(generate_built_in_variable_name_from_span(&src, &raw_src), scope.clone());
     for element in src.clone().into_inner().into_iter() {
         let rule = element.as_rule();
         match rule {
             Rule::Exp => {
-                let if_var = Variable::new_with_type_indication(generate_built_in_variable_name_from_span(&element), Some(element.as_str().to_string()), TypeIndication::Bool);
+                let if_var = Variable::new_with_type_indication(generate_built_in_variable_name_from_span(&element, &raw_src), Some(element.as_str().to_string()), TypeIndication::Bool);
                 {
                     let mut if_var_write = if_var.write().unwrap();
                     if_var_write.set_code_location(CodeLocation::new_from_pest_rule(&element, raw_src.clone()));
@@ -47,7 +47,7 @@ pub fn parse_If(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcInfo
         let mut output_if_write = output_if.write().unwrap();
         output_if_write.set_code_location(CodeLocation::new_from_pest_rule(&src, raw_src.clone()));
     }
-    let output_if_var = Variable::new_builtin(format!("if_{}", generate_built_in_variable_name_from_span(&src)), TypedValue::If(output_if));
+    let output_if_var = Variable::new_builtin(format!("if_{}", generate_built_in_variable_name_from_span(&src, &raw_src)), TypedValue::If(output_if));
     {
         let mut output_if_var_write = output_if_var.write().unwrap();
         output_if_var_write.set_code_location(CodeLocation::new_from_pest_rule(&src, raw_src.clone()));
@@ -57,12 +57,12 @@ pub fn parse_If(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcInfo
 
 #[allow(non_snake_case)]
 pub fn parse_Elif(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcInfo>) -> Result<Elif, TydiLangError> {
-    let mut output_elif = Elif::new(generate_built_in_variable_name_from_span(&src), scope.clone());
+    let mut output_elif = Elif::new(generate_built_in_variable_name_from_span(&src, &raw_src), scope.clone());
     for element in src.clone().into_inner().into_iter() {
         let rule = element.as_rule();
         match rule {
             Rule::Exp => {
-                let elif_var = Variable::new_with_type_indication(generate_built_in_variable_name_from_span(&element), Some(element.as_str().to_string()), TypeIndication::Bool);
+                let elif_var = Variable::new_with_type_indication(generate_built_in_variable_name_from_span(&element, &raw_src), Some(element.as_str().to_string()), TypeIndication::Bool);
                 {
                     let mut elif_var_write = elif_var.write().unwrap();
                     elif_var_write.set_code_location(CodeLocation::new_from_pest_rule(&element, raw_src.clone()));
@@ -82,7 +82,7 @@ pub fn parse_Elif(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcIn
 
 #[allow(non_snake_case)]
 pub fn parse_Else(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcInfo>) -> Result<Else, TydiLangError> {
-    let mut output_else = Else::new(generate_built_in_variable_name_from_span(&src), scope.clone());
+    let mut output_else = Else::new(generate_built_in_variable_name_from_span(&src, &raw_src), scope.clone());
     for element in src.clone().into_inner().into_iter() {
         let rule = element.as_rule();
         match rule {
@@ -99,7 +99,7 @@ pub fn parse_Else(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcIn
 
 #[allow(non_snake_case)]
 pub fn parse_For(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcInfo>) -> Result<Arc<RwLock<Variable>>, TydiLangError> {
-    let output_for = For::new(generate_built_in_variable_name_from_span(&src), scope.clone());
+    let output_for = For::new(generate_built_in_variable_name_from_span(&src, &raw_src), scope.clone());
     for element in src.clone().into_inner().into_iter() {
         let rule = element.as_rule();
         match rule {
@@ -109,7 +109,7 @@ pub fn parse_For(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcInf
                 output_for_write.set_for_var_name(for_var);
             }
             Rule::Exp => {
-                let for_array_var = Variable::new_with_type_indication(generate_built_in_variable_name_from_span(&element), Some(element.as_str().to_string()), TypeIndication::Any);
+                let for_array_var = Variable::new_with_type_indication(generate_built_in_variable_name_from_span(&element, &raw_src), Some(element.as_str().to_string()), TypeIndication::Any);
                 {
                     let mut for_array_var_write = for_array_var.write().unwrap();
                     for_array_var_write.set_code_location(CodeLocation::new_from_pest_rule(&element, raw_src.clone()));
@@ -128,7 +128,7 @@ pub fn parse_For(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcInf
         let mut output_for_write = output_for.write().unwrap();
         output_for_write.set_code_location(CodeLocation::new_from_pest_rule(&src, raw_src.clone()));
     }
-    let output_for_var = Variable::new_builtin(generate_built_in_variable_name_from_span(&src), TypedValue::For(output_for));
+    let output_for_var = Variable::new_builtin(generate_built_in_variable_name_from_span(&src, &raw_src), TypedValue::For(output_for));
     {
         let mut output_for_var_write = output_for_var.write().unwrap();
         output_for_var_write.set_code_location(CodeLocation::new_from_pest_rule(&src, raw_src.clone()));
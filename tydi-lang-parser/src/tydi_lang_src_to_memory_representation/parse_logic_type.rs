@@ -18,12 +18,13 @@ pub fn parse_LogicalBit(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc
             _ => unreachable!()
         }
     }
-    let output_logic_bit = LogicBit::new(generate_name::generate_built_in_variable_name_from_span(&src), Some(bit_exp));
+    let logic_bit_name = generate_name::generate_built_in_variable_name_from_span_in_scope(&src, &raw_src, scope.clone());
+    let output_logic_bit = LogicBit::new(logic_bit_name.clone(), Some(bit_exp));
     {
         let mut output_logic_bit_write = output_logic_bit.write().unwrap();
         output_logic_bit_write.set_code_location(CodeLocation::new_from_pest_rule(&src, raw_src.clone()));
     }
-    let logic_bit_var = Variable::new_logic_type(generate_name::generate_built_in_variable_name_from_span(&src), Arc::new(RwLock::new(LogicType::LogicBitType(output_logic_bit))));
+    let logic_bit_var = Variable::new_logic_type(logic_bit_name, Arc::new(RwLock::new(LogicType::LogicBitType(output_logic_bit))));
     {
         let mut logic_bit_var_write = logic_bit_var.write().unwrap();
         let code_location = CodeLocation::new_from_pest_rule(&src, raw_src.clone());
@@ -56,7 +57,7 @@ pub fn parse_LogicalGroup(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: A
                 template_args = parse_TemplateArgs(element, scope.clone(), raw_src.clone())?;
             }
             Rule::Scope_WithoutBracket => {
-                output_logic_group = LogicGroup::new(generate_name::generate_built_in_variable_name_from_span(&src), scope.clone());
+                output_logic_group = LogicGroup::new(generate_name::generate_built_in_variable_name_from_span(&src, &raw_src), scope.clone());
                 let output_logic_group_read = output_logic_group.read().unwrap();
                 let output_logic_scope = output_logic_group_read.get_scope();
                 parse_Scope_WithoutBracket(element, output_logic_scope.clone(), raw_src.clone())?;
@@ -102,7 +103,7 @@ pub fn parse_LogicalUnion(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: A
                 template_args = parse_TemplateArgs(element, scope.clone(), raw_src.clone())?;
             }
             Rule::Scope_WithoutBracket => {
-                output_logic_union = LogicUnion::new(generate_name::generate_built_in_variable_name_from_span(&src), scope.clone());
+                output_logic_union = LogicUnion::new(generate_name::generate_built_in_variable_name_from_span(&src, &raw_src), scope.clone());
                 let output_logic_union_read = output_logic_union.read().unwrap();
                 let output_logic_scope = output_logic_union_read.get_scope();
                 parse_Scope_WithoutBracket(element, output_logic_scope.clone(), raw_src.clone())?;
@@ -177,7 +178,7 @@ pub fn parse_LogicalStream(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src:
             _ => unreachable!()
         }
     }
-    let output_logic_stream = LogicStream::new(generate_name::generate_built_in_variable_name_from_span(&src), Some(stream_type_exp));
+    let output_logic_stream = LogicStream::new(generate_name::generate_built_in_variable_name_from_span_in_scope(&src, &raw_src, scope.clone()), Some(stream_type_exp));
     let logic_stream_var_name = output_logic_stream.read().unwrap().get_name();
     // add stream property
     {
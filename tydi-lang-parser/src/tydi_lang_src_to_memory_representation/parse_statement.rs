@@ -4,7 +4,7 @@ use crate::error::TydiLangError;
 use crate::trait_common::GetName;
 use crate::tydi_memory_representation::{CodeLocation, Scope, SrcInfo, TraitCodeLocationAccess, TypeIndication, Variable};
 use crate::{tydi_parser::*, generate_name};
-use crate::tydi_lang_src_to_memory_representation::{parse_type, parse_logic_type, parse_streamlet, parse_implementation};
+use crate::tydi_lang_src_to_memory_representation::{parse_type, parse_logic_type, parse_streamlet, parse_implementation, parse_function};
 
 use super::parse_logic_flow::{parse_If, parse_For};
 
@@ -64,6 +64,8 @@ pub fn parse_StatementDeclareType(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, ra
         var_write.set_is_property_of_scope(true);
         var_write.set_code_location(CodeLocation::new_from_pest_rule(&src, raw_src.clone()));
         var_write.set_is_name_user_defined(true);
+        //a declaration without a value has no expression to evaluate
+        var_write.set_exp(None);
 
         //if it is a logic type (excluding LogicNull):
         match &type_indicator {
@@ -73,6 +75,7 @@ pub fn parse_StatementDeclareType(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, ra
             TypeIndication::LogicUnion(v) => {
                 var_write.set_exp(Some(v.read().unwrap().get_name()));
                 var_write.set_type_indication(TypeIndication::AnyLogicType);
+                var_write.set_array_size(v.read().unwrap().get_array_size());
             },
             _ => ()
         }
@@ -139,6 +142,42 @@ pub fn parse_StatementDeclareStreamlet(src: Pair<Rule>, scope: Arc<RwLock<Scope>
     return Ok(());
 }
 
+#[allow(non_snake_case)]
+pub fn parse_StatementDeclareInterface(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcInfo>) -> Result<(), TydiLangError> {
+    for element in src.clone().into_inner().into_iter() {
+        let rule = element.as_rule();
+        match rule {
+            Rule::Interface => {
+                let var = parse_streamlet::parse_StreamLet(element, scope.clone(), raw_src.clone())?;
+                {
+                    let mut scope_write = scope.write().unwrap();
+                    scope_write.add_var(var)?;
+                }
+            }
+            _ => unreachable!()
+        }
+    }
+    return Ok(());
+}
+
+#[allow(non_snake_case)]
+pub fn parse_StatementDeclareFunction(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcInfo>) -> Result<(), TydiLangError> {
+    for element in src.clone().into_inner().into_iter() {
+        let rule = element.as_rule();
+        match rule {
+            Rule::FunctionDefinition => {
+                let var = parse_function::parse_FunctionDefinition(element, scope.clone(), raw_src.clone())?;
+                {
+                    let mut scope_write = scope.write().unwrap();
+                    scope_write.add_var(var)?;
+                }
+            }
+            _ => unreachable!()
+        }
+    }
+    return Ok(());
+}
+
 #[allow(non_snake_case)]
 pub fn parse_StatementDeclarePort(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcInfo>) -> Result<(), TydiLangError> {
     for element in src.clone().into_inner().into_iter() {
@@ -157,6 +196,24 @@ pub fn parse_StatementDeclarePort(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, ra
     return Ok(());
 }
 
+#[allow(non_snake_case)]
+pub fn parse_StatementDeclarePortBundle(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcInfo>) -> Result<(), TydiLangError> {
+    for element in src.clone().into_inner().into_iter() {
+        let rule = element.as_rule();
+        match rule {
+            Rule::PortBundle => {
+                let port_bundle_var = parse_streamlet::parse_PortBundle(element, scope.clone(), raw_src.clone())?;
+                {
+                    let mut scope_write = scope.write().unwrap();
+                    scope_write.add_var(port_bundle_var)?;
+                }
+            }
+            _ => unreachable!()
+        }
+    }
+    return Ok(());
+}
+
 #[allow(non_snake_case)]
 pub fn parse_StatementDeclareImplementation(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcInfo>) -> Result<(), TydiLangError> {
     for element in src.clone().into_inner().into_iter() {
@@ -198,7 +255,7 @@ pub fn parse_StatementDeclareNet(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw
     for element in src.clone().into_inner().into_iter() {
         let rule = element.as_rule();
         match rule {
-            Rule::Net => {
+            Rule::Net | Rule::NetBulk => {
                 let var = parse_implementation::parse_Net(element, scope.clone(), raw_src.clone())?;
                 {
                     let mut scope_write = scope.write().unwrap();
@@ -218,7 +275,7 @@ pub fn parse_StatementFunction(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_s
         match rule {
             Rule::FunctionExp => {
                 let function_exp = element.as_str().to_string();
-                let function_var_name = generate_name::generate_built_in_variable_name_from_span(&element);
+                let function_var_name = generate_name::generate_built_in_variable_name_from_span(&element, &raw_src);
                 let function_var = Variable::new_function_var(function_var_name, function_exp);
                 {
                     let mut function_var_write = function_var.write().unwrap();
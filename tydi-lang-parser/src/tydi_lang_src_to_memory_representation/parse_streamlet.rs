@@ -5,7 +5,7 @@ use crate::error::TydiLangError;
 use crate::generate_name::{generate_init_value, generate_built_in_variable_name_from_span};
 use crate::trait_common::HasDocument;
 use crate::tydi_lang_src_to_memory_representation::parse_type::parse_ArraySizeIndicator;
-use crate::tydi_memory_representation::{Scope, Streamlet, GetScope, Variable, TraitCodeLocationAccess, CodeLocation, Port, TypeIndication, PortDirection, GlobalIdentifier, SrcInfo};
+use crate::tydi_memory_representation::{Scope, Streamlet, GetScope, Variable, TraitCodeLocationAccess, CodeLocation, Port, PortBundle, TypeIndication, PortDirection, GlobalIdentifier, SrcInfo};
 use crate::tydi_parser::*;
 
 use crate::tydi_lang_src_to_memory_representation::{parse_template, parse_miscellaneous, parse_file};
@@ -17,6 +17,8 @@ pub fn parse_StreamLet(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<
     let mut name = generate_init_value();
     let mut template_args = None;
     let mut attributes = vec![];
+    let mut extends_streamlet_exp = None;
+    let mut is_interface = false;
 
     for element in src.clone().into_inner().into_iter() {
         let rule = element.as_rule();
@@ -24,12 +26,19 @@ pub fn parse_StreamLet(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<
             Rule::DOCUMENT_CONTENT => {
                 document = Some(element.as_str().to_string());
             }
+            Rule::InterfaceKeyword => {
+                is_interface = true;
+            }
             Rule::ID => {
                 name = element.as_str().to_string();
             }
             Rule::TemplateArgs => {
                 template_args = parse_template::parse_TemplateArgs(element, scope.clone(), raw_src.clone())?;
             }
+            Rule::StreamletExtends => {
+                let exp = element.clone().into_inner().last().unwrap();
+                extends_streamlet_exp = Some((exp.as_str().to_string(), CodeLocation::new_from_pest_rule(&exp, raw_src.clone())));
+            }
             Rule::ATTRIBUTE => {
                 let attr = parse_miscellaneous::parse_ATTRIBUTE(element, scope.clone(), raw_src.clone())?;
                 attributes.push(attr);
@@ -49,6 +58,10 @@ pub fn parse_StreamLet(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<
         output_streamlet_write.set_code_location(CodeLocation::new_from_pest_rule(&src, raw_src.clone()));
         output_streamlet_write.set_parent_scope(Some(scope.clone()));
         output_streamlet_write.set_id_in_scope(Some(name.clone()));
+        output_streamlet_write.set_is_interface(is_interface);
+        if let Some((exp, exp_code_location)) = extends_streamlet_exp {
+            output_streamlet_write.set_extends_streamlet_exp(exp, exp_code_location);
+        }
     }
 
     let output_streamlet_var = Variable::new_streamlet(name.clone(), output_streamlet);
@@ -80,7 +93,7 @@ pub fn parse_Port(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcIn
                 name = element.as_str().to_string();
             }
             Rule::Exp => {
-                port_logical_type_exp = Variable::new_with_type_indication(generate_built_in_variable_name_from_span(&element), Some(element.as_str().to_string()), TypeIndication::AnyLogicType);
+                port_logical_type_exp = Variable::new_with_type_indication(generate_built_in_variable_name_from_span(&element, &raw_src), Some(element.as_str().to_string()), TypeIndication::AnyLogicType);
                 {
                     let mut port_logical_type_exp_write = port_logical_type_exp.write().unwrap();
                     port_logical_type_exp_write.set_code_location(CodeLocation::new_from_pest_rule(&element, raw_src.clone()));
@@ -110,7 +123,7 @@ pub fn parse_Port(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcIn
         }
     }
 
-    let output_port = Port::new(generate_built_in_variable_name_from_span(&src), port_driection, port_logical_type_exp);
+    let output_port = Port::new(generate_built_in_variable_name_from_span(&src, &raw_src), port_driection, port_logical_type_exp);
     {
         let mut output_port_write = output_port.write().unwrap();
         output_port_write.set_attributes(attributes);
@@ -142,12 +155,64 @@ pub fn parse_Port(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcIn
 }
 
 #[allow(non_snake_case)]
-pub fn parse_PortTimeDomain(src: Pair<Rule>, _scope: Arc<RwLock<Scope>>, _: Arc<SrcInfo>) -> Result<Option<Arc<RwLock<Variable>>>, TydiLangError> {
+pub fn parse_PortBundle(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcInfo>) -> Result<Arc<RwLock<Variable>>, TydiLangError> {
+    let mut document = None;
+    let mut name = generate_init_value();
+    let mut attributes = vec![];
+    let mut interface_exp = None;
+    let mut flipped = false;
+    for element in src.clone().into_inner().into_iter() {
+        let rule = element.as_rule();
+        match rule {
+            Rule::DOCUMENT_CONTENT => {
+                document = Some(element.as_str().to_string());
+            }
+            Rule::ID => {
+                name = element.as_str().to_string();
+            }
+            Rule::PortBundleKeyword => (),
+            Rule::Exp => {
+                interface_exp = Some((element.as_str().to_string(), CodeLocation::new_from_pest_rule(&element, raw_src.clone())));
+            }
+            Rule::PortBundleFlip => {
+                flipped = true;
+            }
+            Rule::ATTRIBUTE => {
+                let attr = parse_miscellaneous::parse_ATTRIBUTE(element, scope.clone(), raw_src.clone())?;
+                attributes.push(attr);
+            }
+            _ => unreachable!()
+        }
+    }
+
+    let (interface_exp, interface_exp_code_location) = interface_exp.unwrap();
+    let output_port_bundle = PortBundle::new(name.clone(), interface_exp.clone(), flipped);
+    {
+        let mut output_port_bundle_write = output_port_bundle.write().unwrap();
+        output_port_bundle_write.set_interface_exp(interface_exp, interface_exp_code_location);
+        output_port_bundle_write.set_attributes(attributes);
+        output_port_bundle_write.set_code_location(CodeLocation::new_from_pest_rule(&src, raw_src.clone()));
+        output_port_bundle_write.set_document(document);
+        output_port_bundle_write.set_parent_scope(Some(scope.clone()));
+        output_port_bundle_write.set_id_in_scope(Some(name.clone()));
+    }
+    let output_port_bundle_var = Variable::new_port_bundle(name.clone(), output_port_bundle);
+    {
+        let mut output_port_bundle_var_write = output_port_bundle_var.write().unwrap();
+        output_port_bundle_var_write.set_code_location(CodeLocation::new_from_pest_rule(&src, raw_src.clone()));
+        output_port_bundle_var_write.set_is_property_of_scope(true);
+        output_port_bundle_var_write.set_is_name_user_defined(true);
+    }
+    return Ok(output_port_bundle_var);
+}
+
+#[allow(non_snake_case)]
+pub fn parse_PortTimeDomain(src: Pair<Rule>, _scope: Arc<RwLock<Scope>>, raw_src: Arc<SrcInfo>) -> Result<Option<Arc<RwLock<Variable>>>, TydiLangError> {
     for element in src.clone().into_inner().into_iter() {
         let rule = element.as_rule();
         match rule {
             Rule::Exp => {
-                let output = Variable::new_with_type_indication(generate_built_in_variable_name_from_span(&src), Some(element.as_str().to_string()), TypeIndication::String);
+                let output = Variable::new_with_type_indication(generate_built_in_variable_name_from_span(&src, &raw_src), Some(element.as_str().to_string()), TypeIndication::String);
                 return Ok(Some(output));
             }
             _ => unreachable!()
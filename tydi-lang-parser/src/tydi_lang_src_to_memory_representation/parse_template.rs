@@ -2,7 +2,8 @@ use std::sync::{Arc, RwLock};
 use std::collections::BTreeMap;
 
 use crate::error::TydiLangError;
-use crate::tydi_memory_representation::{Scope, TemplateArg, TraitCodeLocationAccess, CodeLocation, SrcInfo};
+use crate::trait_common::GetName;
+use crate::tydi_memory_representation::{Scope, TemplateArg, TraitCodeLocationAccess, CodeLocation, SrcInfo, TypeIndication};
 use crate::tydi_parser::*;
 
 use super::parse_type::parse_AllTypeKeyword;
@@ -19,10 +20,36 @@ pub fn parse_TemplateArgs(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: A
                 arg_container.insert(index, arg);
                 index += 1;
             }
+            Rule::TemplateArgKind => {
+                let arg = arg_container.get_mut(&(index - 1)).unwrap();
+                if arg.get_type_indication() != TypeIndication::AnyLogicType || arg.get_is_array() {
+                    return Err(TydiLangError::new(format!("template argument {} is not a type, so it cannot have a logic type constraint", arg.get_name()), CodeLocation::new_from_pest_rule(&element, raw_src.clone())));
+                }
+                let kind = element.into_inner().last().unwrap().as_str().to_string();
+                arg.set_kind_constraint(Some(kind));
+            }
+            Rule::TemplateArgWhere => {
+                let constraint_exp = element.into_inner().last().unwrap().as_str().trim().to_string();
+                arg_container.get_mut(&(index - 1)).unwrap().set_constraint_exp(Some(constraint_exp));
+            }
+            Rule::TemplateArgDefault => {
+                let default_exp = element.into_inner().next().unwrap().as_str().to_string();
+                arg_container.get_mut(&(index - 1)).unwrap().set_default_exp(Some(default_exp));
+            }
             _ => unreachable!()
         }
     }
 
+    //args with a default value must come last, so that positional expressions fill the args without defaults
+    let first_default_index = arg_container.iter().find(|(_, arg)| arg.get_default_exp().is_some()).map(|(index, _)| *index);
+    if let Some(first_default_index) = first_default_index {
+        for (_, arg) in arg_container.range(first_default_index..) {
+            if arg.get_default_exp().is_none() {
+                return Err(TydiLangError::new(format!("template argument {} must have a default value because it follows an argument with a default value", arg.get_name()), CodeLocation::new_from_pest_rule(&src, raw_src.clone())));
+            }
+        }
+    }
+
     if arg_container.is_empty() {
         return Ok(None);
     }
@@ -56,6 +83,9 @@ pub fn parse_Arg_Common(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_src: Arc
                 output_arg.set_name(element.as_str().to_string());
             }
             Rule::AllTypeKeyword => {
+                //the streamlet of "impl of" is checked when the template is expanded
+                let impl_of_exp = element.clone().into_inner().find(|keyword| keyword.as_rule() == Rule::ImplementationKeyword).map(|keyword| keyword.into_inner().last().unwrap().as_str().trim().to_string());
+                output_arg.set_impl_of_exp(impl_of_exp);
                 let (type_indication, is_array) = parse_AllTypeKeyword(element, scope.clone(), raw_src.clone())?;
                 output_arg.set_is_array(is_array);
                 output_arg.set_type_indication(type_indication);
@@ -82,9 +82,15 @@ pub fn parse_BasicTypeKeyword(src: Pair<Rule>, _scope: Arc<RwLock<Scope>>, _: Ar
             Rule::FloatTypeKeyword => {
                 type_indicator = TypeIndication::Float;
             }
+            Rule::ClockDomainTypeKeyword => {
+                type_indicator = TypeIndication::Clockdomain;
+            }
             Rule::LogicalTypeKeyword => {
                 type_indicator = TypeIndication::AnyLogicType;
             }
+            Rule::MapTypeKeyword => {
+                type_indicator = TypeIndication::Map;
+            }
             _ => unreachable!()
         }
     }
@@ -198,20 +204,21 @@ pub fn parse_LogicalType_Array(src: Pair<Rule>, scope: Arc<RwLock<Scope>>, raw_s
         }
     }
 
-    if array_size_var_opt.is_some() {
-        let type_indicator_var = match &type_indicator {
-            TypeIndication::LogicNull => return Err(TydiLangError::new(format!("Logic Null cannot be an arry"), CodeLocation::new_from_pest_rule(&src, raw_src.clone()))),
-            TypeIndication::LogicStream(_) => return Err(TydiLangError::new(format!("Logic Stream cannot be an arry"), CodeLocation::new_from_pest_rule(&src, raw_src.clone()))),
-            TypeIndication::LogicBit(v) => v,
-            TypeIndication::LogicGroup(v) => v,
-            TypeIndication::LogicUnion(v) => v,
-            _ => unreachable!()
-        };
-        {
-            let mut type_indicator_var_write = type_indicator_var.write().unwrap();
-            type_indicator_var_write.set_array_size(array_size_var_opt);
-        }
-    }   //we need to set the value according to the "array_size" during evaluation
+    if array_size_var_opt.is_none() {
+        return Err(TydiLangError::new(format!("the size of a logic type array must be specified"), CodeLocation::new_from_pest_rule(&src, raw_src.clone())));
+    }
+    let type_indicator_var = match &type_indicator {
+        TypeIndication::LogicNull => return Err(TydiLangError::new(format!("Logic Null cannot be an array"), CodeLocation::new_from_pest_rule(&src, raw_src.clone()))),
+        TypeIndication::LogicStream(v) => v,
+        TypeIndication::LogicBit(v) => v,
+        TypeIndication::LogicGroup(v) => v,
+        TypeIndication::LogicUnion(v) => v,
+        _ => unreachable!()
+    };
+    {
+        let mut type_indicator_var_write = type_indicator_var.write().unwrap();
+        type_indicator_var_write.set_array_size(array_size_var_opt);
+    }   //the value is expanded to an array according to the "array_size" during evaluation
 
     return Ok(type_indicator);
 }
@@ -226,7 +233,7 @@ pub fn parse_ArraySizeIndicator(src: Pair<Rule>, _scope: Arc<RwLock<Scope>>, raw
             Rule::Exp => {
                 is_exp_provided = true;
                 // array_size_var = create_variable_from_exp(element, scope.clone())?;
-                let name = generate_name::generate_built_in_variable_name_from_span(&element);
+                let name = generate_name::generate_built_in_variable_name_from_span(&element, &raw_src);
                 array_size_var = Variable::new_with_type_indication(name, Some(element.as_str().to_string()), TypeIndication::Int);
                 {
                     let mut array_size_var_write = array_size_var.write().unwrap();
@@ -54,6 +54,11 @@ pub mod port;
 pub(in crate) use port::*;
 pub use port::{Port, PortDirection};
 
+pub mod port_bundle;
+#[allow(unused_imports)]
+pub(in crate) use port_bundle::*;
+pub use port_bundle::PortBundle;
+
 pub mod implementation;
 #[allow(unused_imports)]
 pub(in crate) use implementation::*;
@@ -72,7 +77,7 @@ pub use net::{Net, PortOwner};
 pub mod function;
 #[allow(unused_imports)]
 pub(in crate) use function::*;
-pub use function::Function;
+pub use function::{Function, FunctionDefinition};
 
 pub mod if_for;
 #[allow(unused_imports)]
@@ -5,8 +5,10 @@ use crate::deep_clone::DeepClone;
 #[derive(Clone, Debug, Serialize, PartialEq)]
 pub enum Attribute {
     NoStrictTypeChecking,
+    StrictTypeChecking,
     External,
     NoTemplateExpansion,
+    ClockDomainCrossing,
 }
 
 impl DeepClone for Attribute {
@@ -22,12 +24,18 @@ impl std::convert::TryFrom<String> for Attribute {
         if value == "NoTypeCheck" || value == "no_type_check" {
             return Ok(Attribute::NoStrictTypeChecking);
         }
+        if value == "StrictTypeCheck" || value == "strict_type_check" {
+            return Ok(Attribute::StrictTypeChecking);
+        }
         if value == "External" || value == "external" {
             return Ok(Attribute::External);
         }
         if value == "NoTemplateExpansion" || value == "no_template_expansion" {
             return Ok(Attribute::NoTemplateExpansion);
         }
+        if value == "ClockDomainCrossing" || value == "clock_domain_crossing" {
+            return Ok(Attribute::ClockDomainCrossing);
+        }
 
         return Err(());
     }
@@ -38,8 +46,10 @@ impl std::convert::TryFrom<Attribute> for String {
     fn try_from(value: Attribute) -> Result<Self, Self::Error> {
         match value {
             Attribute::NoStrictTypeChecking => Ok(String::from("NoStrictTypeChecking")),
+            Attribute::StrictTypeChecking => Ok(String::from("StrictTypeChecking")),
             Attribute::External => Ok(String::from("External")),
             Attribute::NoTemplateExpansion => Ok(String::from("NoTemplateExpansion")),
+            Attribute::ClockDomainCrossing => Ok(String::from("ClockDomainCrossing")),
         }
     }
 }
\ No newline at end of file
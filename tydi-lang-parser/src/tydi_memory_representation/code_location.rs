@@ -2,19 +2,23 @@ use std::{sync::Arc, usize};
 
 use serde::Serialize;
 
-use crate::{tydi_parser::*, generate_name::generate_init_value, deep_clone::DeepClone};
+use crate::{tydi_parser::*, generate_name::{generate_init_value, get_src_tag}, deep_clone::DeepClone};
 
 #[derive(Clone, Debug)]
 pub struct SrcInfo {
     pub file_name: String,
     pub file_content: String,
+    //used in generated names, computed once because hashing the file for every name is slow
+    pub src_tag: String,
 }
 
 impl SrcInfo {
     pub fn new(file_name: String, file_content: String) -> Arc<Self> {
+        let src_tag = get_src_tag(&file_content);
         return Arc::new(Self{
             file_name: file_name,
             file_content: file_content,
+            src_tag: src_tag,
         });
     }
 
@@ -22,6 +26,16 @@ impl SrcInfo {
         return Arc::new(Self{
             file_name: generate_init_value(),
             file_content: generate_init_value(),
+            src_tag: get_src_tag(&generate_init_value()),
+        });
+    }
+
+    // for code without a source file, e.g. code parsed again during evaluation, the caller decides the tag
+    pub fn new_init_with_tag(src_tag: String) -> Arc<Self> {
+        return Arc::new(Self{
+            file_name: generate_init_value(),
+            file_content: generate_init_value(),
+            src_tag: src_tag,
         });
     }
 }
@@ -3,10 +3,10 @@ use std::collections::BTreeMap;
 
 use serde::Serialize;
 
-use crate::deep_clone::DeepClone;
+use crate::deep_clone::{DeepClone, DeepClone_ArcLock};
 use crate::generate_name::generate_init_value;
-use crate::tydi_memory_representation::{CodeLocation, TraitCodeLocationAccess};
-use crate::trait_common::GetName;
+use crate::tydi_memory_representation::{CodeLocation, TraitCodeLocationAccess, TemplateArg, Variable, Scope, ScopeType, GetScope};
+use crate::trait_common::{GetName, HasDocument};
 use crate::{generate_access, generate_get, generate_set, generate_access_pub, generate_get_pub, generate_set_pub};
 
 
@@ -63,3 +63,75 @@ impl Function {
     generate_access_pub!(function_arg_exps, BTreeMap<usize, String>, get_function_arg_exps, set_function_arg_exps);
 }
 
+
+#[derive(Clone, Debug, Serialize)]
+pub struct FunctionDefinition {
+    name: String,
+
+    args: BTreeMap<usize, TemplateArg>,
+
+    // None means the return value is not checked
+    return_type: Option<TemplateArg>,
+
+    #[serde(with = "crate::serde_serialization::use_inner_for_arc_rwlock")]
+    return_exp: Arc<RwLock<Variable>>,
+
+    #[serde(with = "crate::serde_serialization::use_inner_for_arc_rwlock")]
+    function_scope: Arc<RwLock<Scope>>,
+
+    location_define: CodeLocation,
+
+    document: Option<String>,
+}
+
+impl GetName for FunctionDefinition {
+    fn get_name(&self) -> String {
+        return self.name.clone();
+    }
+}
+
+impl TraitCodeLocationAccess for FunctionDefinition {
+    generate_access!(location_define, CodeLocation, get_code_location, set_code_location);
+}
+
+impl HasDocument for FunctionDefinition {
+    generate_access!(document, Option<String>, get_document, set_document);
+}
+
+impl GetScope for FunctionDefinition {
+    generate_get!(function_scope, Arc<RwLock<Scope>>, get_scope);
+}
+
+impl DeepClone for FunctionDefinition {
+    fn deep_clone(&self) -> Self {
+        let output = Self {
+            name: self.name.deep_clone(),
+            args: self.args.deep_clone(),
+            return_type: self.return_type.deep_clone(),
+            return_exp: self.return_exp.deep_clone(),
+            function_scope: self.function_scope.read().unwrap().deep_clone_arclock(),
+            location_define: self.location_define.deep_clone(),
+            document: self.document.deep_clone(),
+        };
+        return output;
+    }
+}
+
+impl FunctionDefinition {
+    pub fn new(name: String, parent_scope: Arc<RwLock<Scope>>) -> Arc<RwLock<Self>> {
+        let output = Self {
+            name: name.clone(),
+            args: BTreeMap::new(),
+            return_type: None,
+            return_exp: Variable::new_place_holder(),
+            function_scope: Scope::new(format!("function_{}", &name), ScopeType::FunctionScope, parent_scope),
+            location_define: CodeLocation::new_unknown(),
+            document: None,
+        };
+        return Arc::new(RwLock::new(output));
+    }
+
+    generate_access_pub!(args, BTreeMap<usize, TemplateArg>, get_args, set_args);
+    generate_access_pub!(return_type, Option<TemplateArg>, get_return_type, set_return_type);
+    generate_access_pub!(return_exp, Arc<RwLock<Variable>>, get_return_exp, set_return_exp);
+}
@@ -34,6 +34,7 @@ pub struct Identifier {
     id: String,
     id_type: IdentifierType,
     template_args: BTreeMap<usize, String>,
+    named_template_args: BTreeMap<String, String>,
     location: CodeLocation,
 }
 
@@ -43,6 +44,7 @@ impl DeepClone for Identifier {
             id: self.id.deep_clone(),
             id_type: self.id_type.deep_clone(),
             template_args: self.template_args.deep_clone(),
+            named_template_args: self.named_template_args.deep_clone(),
             location: self.location.deep_clone(),
         };
         return output;
@@ -50,29 +52,38 @@ impl DeepClone for Identifier {
 }
 
 impl Identifier {
-    pub fn new(id: String, id_type: IdentifierType, template_args: BTreeMap<usize, String>, location: CodeLocation) -> Arc<RwLock<Self>> {
+    pub fn new(id: String, id_type: IdentifierType, template_args: BTreeMap<usize, String>, named_template_args: BTreeMap<String, String>, location: CodeLocation) -> Arc<RwLock<Self>> {
         let output = Self {
             id: id,
             id_type: id_type,
             template_args: template_args,
+            named_template_args: named_template_args,
             location: location,
         };
         return Arc::new(RwLock::new(output));
     }
 
+    fn get_template_args_brief_info(&self) -> String {
+        let mut template_args: Vec<String> = self.template_args.values().map(|v| v.to_string()).collect();
+        for (name, exp) in &self.named_template_args {
+            template_args.push(format!("{}={}", name, exp));
+        }
+        return template_args.join(",");
+    }
+
     pub fn get_brief_info(&self) -> String {
         match &self.id_type {
             IdentifierType::Unknown => {
                 return format!("unknown");
             },
             IdentifierType::FunctionExp(arg_exp) => {
-                return format!("{} <{}> ({})", self.id, self.template_args.values().map(|v| v.to_string()).collect::<Vec<_>>().join(","), arg_exp.values().map(|v| v.to_string()).collect::<Vec<_>>().join(","));
+                return format!("{} <{}> ({})", self.id, self.get_template_args_brief_info(), arg_exp.values().map(|v| v.to_string()).collect::<Vec<_>>().join(","));
             },
             IdentifierType::IndexExp(index_exp) => {
-                return format!("{} <{}> [{}]", self.id, self.template_args.values().map(|v| v.to_string()).collect::<Vec<_>>().join(","), index_exp);
+                return format!("{} <{}> [{}]", self.id, self.get_template_args_brief_info(), index_exp);
             },
             IdentifierType::IdentifierExp => {
-                return format!("{} <{}>", self.id, self.template_args.values().map(|v| v.to_string()).collect::<Vec<_>>().join(","));
+                return format!("{} <{}>", self.id, self.get_template_args_brief_info());
             }
         }
     }
@@ -80,4 +91,5 @@ impl Identifier {
     generate_get_pub!(id, String, get_id);
     generate_get_pub!(id_type, IdentifierType, get_id_type);
     generate_get_pub!(template_args, BTreeMap<usize, String>, get_template_args);
+    generate_get_pub!(named_template_args, BTreeMap<String, String>, get_named_template_args);
 }
\ No newline at end of file
@@ -2,11 +2,15 @@ use std::sync::{Arc, RwLock};
 
 use serde::{Serialize};
 
-use crate::deep_clone::DeepClone;
+use crate::deep_clone::{DeepClone, DeepClone_ArcLock};
 use crate::tydi_memory_representation::{CodeLocation, Attribute, TraitCodeLocationAccess, Variable, TypeIndication, Implementation, Scope, GlobalIdentifier, Port};
-use crate::trait_common::{GetName, HasDocument};
+use crate::trait_common::{GetName, HasDocument, AccessProperty};
 use crate::{generate_access, generate_get, generate_set, generate_access_pub, generate_get_pub, generate_set_pub, generate_name};
 
+const IMPLEMENTATION_VAR_NAME: &str = "implementation";
+
+pub const AVAILABLE_PROPERTIES: [&'static str; 1] = [IMPLEMENTATION_VAR_NAME];
+
 #[derive(Clone, Debug, Serialize, PartialEq)]
 pub enum InstanceType {
     Unknown,
@@ -51,9 +55,15 @@ impl GetName for Instance {
 
 impl DeepClone for Instance {
     fn deep_clone(&self) -> Self {
+        //an evaluated var holds the implementation, so a shallow clone avoids stack overflow
+        //an unevaluated var (e.g. "dbg<T>" in a template) must be cloned, otherwise all template instances share the value evaluated by the first one
+        let derived_impl_var = match self.derived_impl_var.read().unwrap().get_evaluated().is_value_known() {
+            true => self.derived_impl_var.clone(),
+            false => self.derived_impl_var.read().unwrap().deep_clone_arclock(),
+        };
         let output = Self {
             name: self.name.deep_clone(),
-            derived_impl_var: self.derived_impl_var.clone(),    //shallow clone should be enough, avoid stack overflow
+            derived_impl_var: derived_impl_var,
             derived_impl: self.derived_impl.clone(),            //shallow clone should be enough, avoid stack overflow
             inst_type: self.inst_type.deep_clone(),
             location_define: self.location_define.deep_clone(),
@@ -74,6 +84,15 @@ impl TraitCodeLocationAccess for Instance {
     generate_access!(location_define, CodeLocation, get_code_location, set_code_location);
 }
 
+impl AccessProperty for Instance {
+    fn access_porperty(&self, property_name: &String) -> Option<Arc<RwLock<Variable>>> {
+        if property_name == IMPLEMENTATION_VAR_NAME {
+            return Some(self.derived_impl_var.clone());
+        }
+        return None;
+    }
+}
+
 impl GlobalIdentifier for Instance {
     generate_access!(parent_scope, Option<Arc<RwLock<Scope>>>, get_parent_scope, set_parent_scope);
     generate_access!(id_in_scope, Option<String>, get_id_in_scope, set_id_in_scope);
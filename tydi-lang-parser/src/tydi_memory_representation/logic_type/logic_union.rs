@@ -4,10 +4,14 @@ use std::collections::BTreeMap;
 use serde::{Serialize};
 
 use crate::deep_clone::{DeepClone, DeepClone_ArcLock};
-use crate::tydi_memory_representation::{Scope, GetScope, ScopeType, CodeLocation, TraitCodeLocationAccess, TemplateArg, GlobalIdentifier};
-use crate::trait_common::{GetName, HasDocument};
+use crate::tydi_memory_representation::{Variable, TypedValue, Scope, GetScope, ScopeType, CodeLocation, TraitCodeLocationAccess, TemplateArg, GlobalIdentifier};
+use crate::trait_common::{GetName, HasDocument, AccessProperty};
 use crate::{generate_access, generate_get, generate_set, generate_name, generate_access_pub, generate_get_pub, generate_set_pub};
 
+const ELEMENT_COUNT_VAR_NAME: &str = "count";
+
+pub const AVAILABLE_PROPERTIES: [&'static str; 1] = [ELEMENT_COUNT_VAR_NAME];
+
 #[derive(Clone, Debug, Serialize)]
 pub struct LogicUnion {
     name: String,
@@ -59,6 +63,17 @@ impl GetScope for LogicUnion {
     generate_get!(scope, Arc<RwLock<Scope>>, get_scope);
 }
 
+impl AccessProperty for LogicUnion {
+    fn access_porperty(&self, property_name: &String) -> Option<Arc<RwLock<Variable>>> {
+        if property_name == ELEMENT_COUNT_VAR_NAME {
+            let variables = self.scope.read().unwrap().get_variables();
+            let element_count = variables.iter().filter(|(_, var)| var.read().unwrap().get_is_property_of_scope()).count();
+            return Some(Variable::new_predefined(format!("{}_{}", ELEMENT_COUNT_VAR_NAME, self.name), TypedValue::IntValue(element_count as i128)));
+        }
+        return None;
+    }
+}
+
 impl GlobalIdentifier for LogicUnion {
     generate_access!(parent_scope, Option<Arc<RwLock<Scope>>>, get_parent_scope, set_parent_scope);
     generate_access!(id_in_scope, Option<String>, get_id_in_scope, set_id_in_scope);
@@ -49,6 +49,9 @@ pub struct Net {
     document: Option<String>,
 
     attributes: Vec<Attribute>,
+
+    //a bulk net ("a.* => b.*") connects all same-named ports, the source and the sink are port owners
+    is_bulk: bool,
 }
 
 impl GetName for Net {
@@ -72,6 +75,7 @@ impl DeepClone for Net {
             location_define: self.location_define.deep_clone(),
             document: self.document.deep_clone(),
             attributes: self.attributes.deep_clone(),
+            is_bulk: self.is_bulk,
         };
         return output;
     }
@@ -103,6 +107,7 @@ impl Net {
             location_define: CodeLocation::new_unknown(),
             document: None,
             attributes: vec![],
+            is_bulk: false,
         };
         return Arc::new(RwLock::new(output));
     }
@@ -124,6 +129,7 @@ impl Net {
             location_define: CodeLocation::new_unknown(),
             document: None,
             attributes: vec![],
+            is_bulk: false,
         };
         return Arc::new(RwLock::new(output));
     }
@@ -137,5 +143,6 @@ impl Net {
     generate_access_pub!(sink_port, Option<Arc<RwLock<Port>>>, get_sink_port, set_sink_port);
     generate_access_pub!(sink_port_owner, PortOwner, get_sink_port_owner, set_sink_port_owner);
     generate_access_pub!(net_name, Option<Arc<RwLock<Variable>>>, get_net_name, set_net_name);
+    generate_access_pub!(is_bulk, bool, get_is_bulk, set_is_bulk);
     generate_access_pub!(parent_impl, Option<Arc<RwLock<Implementation>>>, get_parent_impl, set_parent_impl);
 }
\ No newline at end of file
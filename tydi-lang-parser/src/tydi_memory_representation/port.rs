@@ -5,9 +5,15 @@ use serde::{Serialize, Deserialize};
 use crate::deep_clone::DeepClone;
 use crate::tydi_memory_representation::{Variable, Attribute, CodeLocation, TraitCodeLocationAccess, TypedValue, Streamlet, Scope, GlobalIdentifier};
 
-use crate::trait_common::{GetName, HasDocument};
+use crate::trait_common::{GetName, HasDocument, AccessProperty};
 use crate::{generate_access, generate_get, generate_set, generate_name, generate_access_pub, generate_get_pub, generate_set_pub};
 
+const DIRECTION_VAR_NAME: &str = "direction";
+const CLOCK_DOMAIN_VAR_NAME: &str = "clock_domain";
+const LOGIC_TYPE_VAR_NAME: &str = "logic_type";
+
+pub const AVAILABLE_PROPERTIES: [&'static str; 3] = [DIRECTION_VAR_NAME, CLOCK_DOMAIN_VAR_NAME, LOGIC_TYPE_VAR_NAME];
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum PortDirection {
     In,
@@ -29,6 +35,14 @@ impl PortDirection {
             PortDirection::Unknown => String::from("Unknown"),
         };
     }
+
+    pub fn flip(&self) -> PortDirection {
+        return match self {
+            PortDirection::In => PortDirection::Out,
+            PortDirection::Out => PortDirection::In,
+            PortDirection::Unknown => PortDirection::Unknown,
+        };
+    }
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -94,6 +108,21 @@ impl TraitCodeLocationAccess for Port {
     generate_access!(location_define, CodeLocation, get_code_location, set_code_location);
 }
 
+impl AccessProperty for Port {
+    fn access_porperty(&self, property_name: &String) -> Option<Arc<RwLock<Variable>>> {
+        if property_name == DIRECTION_VAR_NAME {
+            return Some(Variable::new_predefined(format!("{}_{}", DIRECTION_VAR_NAME, self.name), TypedValue::StringValue(self.direction.to_string())));
+        }
+        if property_name == CLOCK_DOMAIN_VAR_NAME {
+            return Some(self.time_domain.clone());
+        }
+        if property_name == LOGIC_TYPE_VAR_NAME {
+            return Some(self.logical_type.clone());
+        }
+        return None;
+    }
+}
+
 impl Port {
     pub fn new(name: String, direction: PortDirection, logical_type: Arc<RwLock<Variable>>) -> Arc<RwLock<Self>> {
         let output = Self {
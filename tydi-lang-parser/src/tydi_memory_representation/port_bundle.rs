@@ -0,0 +1,126 @@
+use std::sync::{Arc, RwLock};
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::deep_clone::DeepClone;
+use crate::tydi_memory_representation::{Variable, Attribute, CodeLocation, TraitCodeLocationAccess, TypeIndication, Port, Scope, GlobalIdentifier};
+
+use crate::trait_common::{GetName, HasDocument};
+use crate::{generate_access, generate_get, generate_set, generate_name, generate_access_pub, generate_get_pub, generate_set_pub};
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PortBundle {
+    name: String,
+
+    #[serde(with = "crate::serde_serialization::use_name_for_arc_rwlock")]
+    interface_var: Arc<RwLock<Variable>>,
+
+    flipped: bool,
+
+    //the ports expanded from the interface, the key is the port name in the interface
+    //they are also ordinary variables of the streamlet, so they are serialized there
+    #[serde(skip)]
+    ports: BTreeMap<String, Arc<RwLock<Port>>>,
+
+    attributes: Vec<Attribute>,
+
+    document: Option<String>,
+
+    location_define: CodeLocation,
+
+    #[serde(with = "crate::serde_serialization::use_name_for_optional_arc_rwlock")]
+    parent_scope: Option<Arc<RwLock<Scope>>>,
+    id_in_scope: Option<String>,
+}
+
+impl GetName for PortBundle {
+    fn get_name(&self) -> String {
+        return self.name.clone();
+    }
+}
+
+impl GlobalIdentifier for PortBundle {
+    generate_access!(parent_scope, Option<Arc<RwLock<Scope>>>, get_parent_scope, set_parent_scope);
+    generate_access!(id_in_scope, Option<String>, get_id_in_scope, set_id_in_scope);
+}
+
+impl DeepClone for PortBundle {
+    fn deep_clone(&self) -> Self {
+        let output = Self {
+            name: self.name.deep_clone(),
+            interface_var: self.interface_var.deep_clone(),
+            flipped: self.flipped,
+            ports: BTreeMap::new(),     //the ports are expanded again when the cloned bundle is evaluated
+            attributes: self.attributes.deep_clone(),
+            document: self.document.deep_clone(),
+            location_define: self.location_define.deep_clone(),
+            parent_scope: self.parent_scope.clone(),
+            id_in_scope: self.id_in_scope.deep_clone(),
+        };
+        return output;
+    }
+}
+
+impl HasDocument for PortBundle {
+    generate_access!(document, Option<String>, get_document, set_document);
+}
+
+impl TraitCodeLocationAccess for PortBundle {
+    generate_access!(location_define, CodeLocation, get_code_location, set_code_location);
+}
+
+impl PortBundle {
+    pub fn new(name: String, interface_exp: String, flipped: bool) -> Arc<RwLock<Self>> {
+        let mut output = Self {
+            name: name.clone(),
+            interface_var: Variable::new_place_holder(),
+            flipped: flipped,
+            ports: BTreeMap::new(),
+            attributes: vec![],
+            document: None,
+            location_define: CodeLocation::new_unknown(),
+            parent_scope: None,
+            id_in_scope: None,
+        };
+        output.set_interface_exp(interface_exp, CodeLocation::new_unknown());
+        return Arc::new(RwLock::new(output));
+    }
+
+    pub fn new_place_holder() -> Arc<RwLock<Self>> {
+        let output = Self {
+            name: generate_name::generate_init_value(),
+            interface_var: Variable::new_place_holder(),
+            flipped: false,
+            ports: BTreeMap::new(),
+            attributes: vec![],
+            document: None,
+            location_define: CodeLocation::new_unknown(),
+            parent_scope: None,
+            id_in_scope: None,
+        };
+        return Arc::new(RwLock::new(output));
+    }
+
+    pub fn get_brief_info(&self) -> String {
+        let flipped_str = if self.flipped { " flip" } else { "" };
+        return format!("PortBundle({}){{{}}}{}", &self.name, self.ports.keys().map(|i| i.to_string()).collect::<Vec<_>>().join(", "), flipped_str);
+    }
+
+    generate_set_pub!(name, String, set_name);
+    generate_access_pub!(interface_var, Arc<RwLock<Variable>>, get_interface_var, set_interface_var);
+    generate_access_pub!(flipped, bool, get_flipped, set_flipped);
+    generate_access_pub!(ports, BTreeMap<String, Arc<RwLock<Port>>>, get_ports, set_ports);
+    generate_access_pub!(attributes, Vec<Attribute>, get_attributes, set_attributes);
+    generate_access_pub!(id_in_scope, Option<String>, get_id_in_scope, set_id_in_scope);
+
+    pub fn set_interface_exp(&mut self, interface_exp: String, code_location: CodeLocation) {
+        let interface_var = Variable::new(format!("interface_exp_of_{}", self.name.clone()), Some(interface_exp));
+        {
+            let mut interface_var_write = interface_var.write().unwrap();
+            interface_var_write.set_code_location(code_location);
+            interface_var_write.set_type_indication(TypeIndication::AnyStreamlet);
+        }
+        self.interface_var = interface_var;
+    }
+}
@@ -8,6 +8,9 @@ use crate::evaluation::{Evaluator, evaluate_var, EvaluationTrace};
 use crate::{generate_get_pub, generate_set_pub, generate_access_pub};
 use crate::tydi_memory_representation::{Package, CodeLocation, GetScope, Scope, ScopeRelationType, Variable, SrcInfo};
 
+// the stack size of the evaluation thread, enough for MAX_FUNCTION_CALL_DEEPTH nested function calls in a debug build
+pub const EVALUATION_STACK_SIZE: usize = 64 * 1024 * 1024;
+
 #[derive(Clone, Debug, Serialize)]
 pub struct ProjectItem {
     pub item_name: String,
@@ -68,7 +71,23 @@ impl Project {
         return json_output;
     }
 
+    pub fn new_evaluator(&self) -> Arc<RwLock<Evaluator>> {
+        match &self.self_arc {
+            Some(self_arc) => return Evaluator::new(self_arc.clone()),
+            None => unreachable!(),
+        }
+    }
+
     pub fn evaluate_target(&self, target_name: String, package_name: String) -> Result<Arc<RwLock<Evaluator>>, TydiLangError> {
+        let evaluator = self.new_evaluator();
+
+        self.evaluate_target_with_evaluator(target_name, package_name, evaluator.clone())?;
+
+        return Ok(evaluator);
+    }
+
+    /// the diagnostics reported by the source code stay in the given evaluator, even if the evaluation fails
+    pub fn evaluate_target_with_evaluator(&self, target_name: String, package_name: String, evaluator: Arc<RwLock<Evaluator>>) -> Result<(), TydiLangError> {
         let target_package = self.packages.get(&package_name);
         if target_package.is_none() {
             return Err(TydiLangError::new(format!("no such package: {}", &package_name), CodeLocation::new_unknown()));
@@ -76,17 +95,23 @@ impl Project {
         let target_package = target_package.unwrap();
         let target_package_scope = target_package.read().unwrap().get_scope();
 
-        let evaluator = match &self.self_arc {
-            Some(self_arc) => Evaluator::new(self_arc.clone()),
-            None => unreachable!(),
-        };
-
         evaluator.write().unwrap().add_trace(EvaluationTrace::new_region_begin(format!("evaluation")));
         let (target_var, target_var_scope) = Scope::resolve_identifier(&target_name, &None, &CodeLocation::new_unknown(), target_package_scope.clone(), target_package_scope.clone(), ScopeRelationType::resolve_id_default(), evaluator.clone())?;
-        evaluate_var(target_var.clone(), target_var_scope.clone(), evaluator.clone())?;
+        //evaluation recurses once per nested function call, so it runs on its own thread with a stack that doesn't depend on the caller
+        let evaluation_result = std::thread::scope(|thread_scope| {
+            let evaluation_thread = std::thread::Builder::new().stack_size(EVALUATION_STACK_SIZE).spawn_scoped(thread_scope, || {
+                evaluate_var(target_var.clone(), target_var_scope.clone(), evaluator.clone())
+            }).expect("fail to start the evaluation thread");
+            match evaluation_thread.join() {
+                Ok(result) => result,
+                Err(panic) => std::panic::resume_unwind(panic),
+            }
+        });
+        evaluation_result?;
         evaluator.write().unwrap().add_trace(EvaluationTrace::new_region_end(format!("evaluation")));
 
-        return Ok(evaluator);
+        evaluator.read().unwrap().check_error_diagnostics()?;
+        return Ok(());
     }
 
     pub fn get_variable(&self, package_name: String, target_name: String) -> Result<Arc<RwLock<Variable>>, String> {
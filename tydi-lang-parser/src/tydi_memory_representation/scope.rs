@@ -19,8 +19,10 @@ pub enum ScopeRelationType {
     StreamletScopeRela,
     ImplementationScopeRela,
     IfForScopeRela,
+    FunctionScopeRela,
 
     ImplToStreamletRela,
+    StreamletExtendsRela,
 
     ParentScopeRela, // a placeholder, should never be used
 }
@@ -33,6 +35,8 @@ impl ScopeRelationType {
         output.insert(ScopeRelationType::StreamletScopeRela);
         output.insert(ScopeRelationType::ImplementationScopeRela);
         output.insert(ScopeRelationType::IfForScopeRela);
+        output.insert(ScopeRelationType::FunctionScopeRela);
+        output.insert(ScopeRelationType::StreamletExtendsRela);
         return output;
     }
 
@@ -44,6 +48,7 @@ impl ScopeRelationType {
     pub fn resolve_id_in_parent_streamlet() -> HashSet<ScopeRelationType> {
         let mut output = HashSet::new();
         output.insert(ScopeRelationType::ImplToStreamletRela);
+        output.insert(ScopeRelationType::StreamletExtendsRela);
         return output;
     }
 }
@@ -59,6 +64,7 @@ pub enum ScopeType {
     StreamletScope,
     ImplementationScope,
     IfForScope,
+    FunctionScope,
 
     ParentScope, // a placeholder, should never be used
     UnknownScope,
@@ -183,6 +189,10 @@ impl DeepClone_ArcLock for Scope {
                         let for_scope = target_for.read().unwrap().get_scope();
                         remove_old_scope_add_new_scope(for_scope.clone(), ScopeRelationType::IfForScopeRela, output.clone());
                     },
+                    TypedValue::FunctionDefinition(target_function) => {
+                        let function_scope = target_function.read().unwrap().get_scope();
+                        remove_old_scope_add_new_scope(function_scope.clone(), ScopeRelationType::FunctionScopeRela, output.clone());
+                    },
                     _ => (),    //other typed values don't have scope
                 }
             }
@@ -315,25 +325,43 @@ impl Scope {
             ScopeType::StreamletScope => ScopeRelationType::StreamletScopeRela,
             ScopeType::ImplementationScope => ScopeRelationType::ImplementationScopeRela,
             ScopeType::IfForScope => ScopeRelationType::IfForScopeRela,
+            ScopeType::FunctionScope => ScopeRelationType::FunctionScopeRela,
             ScopeType::ParentScope => ScopeRelationType::ParentScopeRela,
             ScopeType::UnknownScope => todo!(),
         }
     }
 
     //resolve identifier
-    pub fn resolve_identifier(name: &String, template_exps: &Option<BTreeMap<usize, TypedValue>>, location: &CodeLocation, scope: Arc<RwLock<Scope>>, template_expansion_scope: Arc<RwLock<Scope>>, scope_relation_types/*allowed edges*/: HashSet<ScopeRelationType>, evaluator: Arc<RwLock<Evaluator>>) -> Result<(Arc<RwLock<Variable>>, Arc<RwLock<Scope>>), TydiLangError> {        
+    pub fn resolve_identifier(name: &String, template_exps: &Option<BTreeMap<usize, TypedValue>>, location: &CodeLocation, scope: Arc<RwLock<Scope>>, template_expansion_scope: Arc<RwLock<Scope>>, scope_relation_types/*allowed edges*/: HashSet<ScopeRelationType>, evaluator: Arc<RwLock<Evaluator>>) -> Result<(Arc<RwLock<Variable>>, Arc<RwLock<Scope>>), TydiLangError> {
+        return Scope::resolve_identifier_with_named_template_exps(name, template_exps, &BTreeMap::new(), location, scope, template_expansion_scope, scope_relation_types, evaluator);
+    }
+
+    pub fn resolve_identifier_with_named_template_exps(name: &String, template_exps: &Option<BTreeMap<usize, TypedValue>>, named_template_exps: &BTreeMap<String, TypedValue>, location: &CodeLocation, scope: Arc<RwLock<Scope>>, template_expansion_scope: Arc<RwLock<Scope>>, scope_relation_types/*allowed edges*/: HashSet<ScopeRelationType>, evaluator: Arc<RwLock<Evaluator>>) -> Result<(Arc<RwLock<Variable>>, Arc<RwLock<Scope>>), TydiLangError> {
         //does current scope has this var?
-        let result = Scope::resolve_identifier_in_current_scope(&name, &template_exps, location, scope.clone(), template_expansion_scope.clone(), evaluator.clone())?;
+        let result = Scope::resolve_identifier_in_current_scope(&name, &template_exps, named_template_exps, location, scope.clone(), template_expansion_scope.clone(), evaluator.clone())?;
         if result.is_some() {
             return Ok((result.unwrap(), scope.clone()));
         }
 
         //how about other scopes?
         let other_scope_relationships = scope.read().unwrap().get_scope_relationships().clone();
+
+        //a streamlet inherits the variables of the streamlet it extends, they are found before the variables of the outer scopes
+        if scope_relation_types.contains(&ScopeRelationType::StreamletExtendsRela) {
+            for (_, item) in &other_scope_relationships {
+                if item.relationship == ScopeRelationType::StreamletExtendsRela && Scope::is_declared_in_extended_scope(name, item.target_scope.clone()) {
+                    return Scope::resolve_identifier_with_named_template_exps(name, template_exps, named_template_exps, location, item.target_scope.clone(), template_expansion_scope.clone(), scope_relation_types, evaluator.clone());
+                }
+            }
+        }
+
         for (_, item) in other_scope_relationships {
             let (other_scope, relationship_type) = (item.target_scope, item.relationship);
+            if relationship_type == ScopeRelationType::StreamletExtendsRela {
+                continue;   //already checked above
+            }
             if scope_relation_types.contains(&relationship_type) {
-                let result = Scope::resolve_identifier(name, template_exps, location, other_scope, template_expansion_scope.clone(), scope_relation_types, evaluator.clone())?;
+                let result = Scope::resolve_identifier_with_named_template_exps(name, template_exps, named_template_exps, location, other_scope, template_expansion_scope.clone(), scope_relation_types, evaluator.clone())?;
                 return Ok(result);
             }
         }
@@ -341,14 +369,22 @@ impl Scope {
         return Err(TydiLangError::new(format!("identifier {} not found in scope {}", &name, scope.read().unwrap().get_name()), location.clone()));
     }
 
-    fn resolve_identifier_in_current_scope(name: &String, template_exps: &Option<BTreeMap<usize, TypedValue>>, location: &CodeLocation, scope: Arc<RwLock<Scope>>, template_expansion_scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<Option<Arc<RwLock<Variable>>>, TydiLangError> {
+    fn is_declared_in_extended_scope(name: &String, scope: Arc<RwLock<Scope>>) -> bool {
+        let scope_read = scope.read().unwrap();
+        if scope_read.get_variables_ref().contains_key(name) {
+            return true;
+        }
+        return scope_read.get_scope_relationships().values().any(|item| item.relationship == ScopeRelationType::StreamletExtendsRela && Scope::is_declared_in_extended_scope(name, item.target_scope.clone()));
+    }
+
+    fn resolve_identifier_in_current_scope(name: &String, template_exps: &Option<BTreeMap<usize, TypedValue>>, named_template_exps: &BTreeMap<String, TypedValue>, location: &CodeLocation, scope: Arc<RwLock<Scope>>, template_expansion_scope: Arc<RwLock<Scope>>, evaluator: Arc<RwLock<Evaluator>>) -> Result<Option<Arc<RwLock<Variable>>>, TydiLangError> {
         let identifier_var = match scope.read().unwrap().get_variables_ref().get(name) {
             Some(var) => var.clone(),
             None => return Ok(None),
         };
 
         //this is a template instance
-        let output_var = template_expansion::try_template_expansion(identifier_var.clone(), template_exps, template_expansion_scope.clone(), evaluator.clone())?;
+        let output_var = template_expansion::try_template_expansion(identifier_var.clone(), template_exps, named_template_exps, location, template_expansion_scope.clone(), evaluator.clone())?;
         return Ok(Some(output_var));
     }
 }
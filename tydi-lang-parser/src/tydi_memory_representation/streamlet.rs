@@ -4,7 +4,7 @@ use std::collections::BTreeMap;
 use serde::{Serialize};
 
 use crate::deep_clone::{DeepClone, DeepClone_ArcLock};
-use crate::tydi_memory_representation::{TemplateArg, CodeLocation, Scope, ScopeType, GetScope, Attribute, TraitCodeLocationAccess, TypedValue, GlobalIdentifier, Port};
+use crate::tydi_memory_representation::{TemplateArg, CodeLocation, Scope, ScopeType, GetScope, Attribute, TraitCodeLocationAccess, TypedValue, GlobalIdentifier, Port, Variable, TypeIndication};
 use crate::trait_common::{GetName, HasDocument};
 use crate::{generate_access, generate_get, generate_set, generate_access_pub, generate_get_pub, generate_set_pub, generate_name};
 
@@ -25,6 +25,15 @@ pub struct Streamlet {
 
     template_args: Option<BTreeMap<usize, TemplateArg>>,
 
+    #[serde(with = "crate::serde_serialization::use_name_for_optional_arc_rwlock")]
+    extends_streamlet_var: Option<Arc<RwLock<Variable>>>,
+
+    #[serde(with = "crate::serde_serialization::use_name_for_optional_arc_rwlock")]
+    extends_streamlet: Option<Arc<RwLock<Streamlet>>>,
+
+    //an interface is a streamlet that is only used as a port bundle
+    is_interface: bool,
+
     attributes: Vec<Attribute>,
 }
 
@@ -44,6 +53,9 @@ impl DeepClone for Streamlet {
             id_in_scope: self.id_in_scope.clone(),
             document: self.document.deep_clone(),
             template_args: self.template_args.deep_clone(),
+            extends_streamlet_var: self.extends_streamlet_var.deep_clone(),
+            extends_streamlet: self.extends_streamlet.deep_clone(),
+            is_interface: self.is_interface,
             attributes: self.attributes.deep_clone(),
         };
         return output;
@@ -77,6 +89,9 @@ impl Streamlet {
             id_in_scope: None,
             document: None,
             template_args: None,
+            extends_streamlet_var: None,
+            extends_streamlet: None,
+            is_interface: false,
             attributes: vec![],
         };
         return Arc::new(RwLock::new(output));
@@ -91,6 +106,9 @@ impl Streamlet {
             id_in_scope: None,
             document: None,
             template_args: None,
+            extends_streamlet_var: None,
+            extends_streamlet: None,
+            is_interface: false,
             attributes: vec![],
         };
         return Arc::new(RwLock::new(output));
@@ -111,11 +129,25 @@ impl Streamlet {
             }
         }
 
-        return format!("Streamlet({}){{{}}}", &self.name, ports_strs.iter().map(|i| i.to_string()).collect::<Vec<_>>().join(", "));
+        let kind_str = if self.is_interface { "Interface" } else { "Streamlet" };
+        return format!("{}({}){{{}}}", kind_str, &self.name, ports_strs.iter().map(|i| i.to_string()).collect::<Vec<_>>().join(", "));
     }
 
     generate_access_pub!(template_args, Option<BTreeMap<usize, TemplateArg>>, get_template_args, set_template_args);
     generate_access_pub!(attributes, Vec<Attribute>, get_attributes, set_attributes);
+    generate_access_pub!(extends_streamlet_var, Option<Arc<RwLock<Variable>>>, get_extends_streamlet_var, set_extends_streamlet_var);
+    generate_access_pub!(extends_streamlet, Option<Arc<RwLock<Streamlet>>>, get_extends_streamlet, set_extends_streamlet);
+    generate_access_pub!(is_interface, bool, get_is_interface, set_is_interface);
+
+    pub fn set_extends_streamlet_exp(&mut self, streamlet_exp: String, code_location: CodeLocation) {
+        let streamlet_var = Variable::new(format!("extends_streamlet_exp_of_{}", self.name.clone()), Some(streamlet_exp));
+        {
+            let mut streamlet_var_write = streamlet_var.write().unwrap();
+            streamlet_var_write.set_type_indication(TypeIndication::AnyStreamlet);
+            streamlet_var_write.set_code_location(code_location);
+        }
+        self.extends_streamlet_var = Some(streamlet_var);
+    }
 }
 
 //interfaces for quick access
@@ -14,6 +14,10 @@ pub struct TemplateArg {
 
     is_array: bool,
     type_indication: TypeIndication,
+    default_exp: Option<String>,
+    kind_constraint: Option<String>,
+    constraint_exp: Option<String>,
+    impl_of_exp: Option<String>,
 
     declare_location: CodeLocation,
 }
@@ -28,6 +32,10 @@ impl DeepClone for TemplateArg {
             name: self.name.deep_clone(),
             is_array: self.is_array.clone(),
             type_indication: self.type_indication.deep_clone(),
+            default_exp: self.default_exp.clone(),
+            kind_constraint: self.kind_constraint.clone(),
+            constraint_exp: self.constraint_exp.clone(),
+            impl_of_exp: self.impl_of_exp.clone(),
             declare_location: self.declare_location.deep_clone(),
         };
         return output;
@@ -44,6 +52,10 @@ impl TemplateArg {
             name: name,
             is_array: false,
             type_indication: type_indication,
+            default_exp: None,
+            kind_constraint: None,
+            constraint_exp: None,
+            impl_of_exp: None,
             declare_location: CodeLocation::new_unknown(),
         };
     }
@@ -53,6 +65,10 @@ impl TemplateArg {
             name: generate_name::generate_init_value(),
             is_array: false,
             type_indication: TypeIndication::Unknown,
+            default_exp: None,
+            kind_constraint: None,
+            constraint_exp: None,
+            impl_of_exp: None,
             declare_location: CodeLocation::new_unknown(),
         };
     }
@@ -60,4 +76,8 @@ impl TemplateArg {
     generate_set_pub!(name, String, set_name);
     generate_access_pub!(is_array, bool, get_is_array, set_is_array);
     generate_access_pub!(type_indication, TypeIndication, get_type_indication, set_type_indication);
+    generate_access_pub!(default_exp, Option<String>, get_default_exp, set_default_exp);
+    generate_access_pub!(kind_constraint, Option<String>, get_kind_constraint, set_kind_constraint);
+    generate_access_pub!(constraint_exp, Option<String>, get_constraint_exp, set_constraint_exp);
+    generate_access_pub!(impl_of_exp, Option<String>, get_impl_of_exp, set_impl_of_exp);
 }
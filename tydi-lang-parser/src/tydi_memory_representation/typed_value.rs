@@ -1,4 +1,5 @@
 use std::sync::{Arc, RwLock};
+use std::collections::BTreeMap;
 
 use serde::Serialize;
 use serde::ser::SerializeStruct;
@@ -6,7 +7,7 @@ use serde::ser::SerializeStruct;
 use crate::deep_clone::DeepClone;
 use crate::{tydi_memory_representation::{Package, LogicType}, trait_common::GetName};
 
-use crate::tydi_memory_representation::{Variable, Streamlet, Port, Implementation, Instance, Net, If, For, Identifier, GetScope, Function};
+use crate::tydi_memory_representation::{Variable, Streamlet, Port, PortBundle, Implementation, Instance, Net, If, For, Identifier, GetScope, Function, FunctionDefinition};
 
 #[derive(Clone, Debug, Serialize)]
 pub enum TypeIndication {
@@ -39,6 +40,7 @@ pub enum TypeIndication {
 
     AnyStreamlet,
     AnyPort,
+    AnyPortBundle,
 
     AnyImplementation,
     AnyInstance,
@@ -49,6 +51,8 @@ pub enum TypeIndication {
     Function,
 
     Array(Box<TypeIndication>),
+
+    Map,
 }
 
 impl DeepClone for TypeIndication {
@@ -71,12 +75,14 @@ impl DeepClone for TypeIndication {
             TypeIndication::LogicTypeRef(v) => TypeIndication::LogicTypeRef(v.deep_clone()),
             TypeIndication::AnyStreamlet => self.clone(),
             TypeIndication::AnyPort => self.clone(),
+            TypeIndication::AnyPortBundle => self.clone(),
             TypeIndication::AnyImplementation => self.clone(),
             TypeIndication::AnyInstance => self.clone(),
             TypeIndication::AnyNet => self.clone(),
             TypeIndication::PackageReference => self.clone(),
             TypeIndication::Function => self.clone(),
             TypeIndication::Array(v) => TypeIndication::Array(Box::new(v.deep_clone())),
+            TypeIndication::Map => self.clone(),
         };
         return output;
     }
@@ -102,12 +108,14 @@ impl std::string::ToString for TypeIndication {
             TypeIndication::LogicTypeRef(v) => format!("logic_type_ref({})", v),
             TypeIndication::AnyStreamlet => format!("any_streamlet"),
             TypeIndication::AnyPort => format!("any_port"),
+            TypeIndication::AnyPortBundle => format!("any_port_bundle"),
             TypeIndication::AnyImplementation => format!("any_implementation"),
             TypeIndication::AnyInstance => format!("any_instance"),
             TypeIndication::AnyNet => format!("any_net"),
             TypeIndication::PackageReference => format!("package_reference"),
             TypeIndication::Function => format!("function"),
             TypeIndication::Array(v) => format!("array({})", v.to_string()),
+            TypeIndication::Map => format!("map"),
         }
     }
 }
@@ -119,6 +127,8 @@ impl PartialEq for TypeIndication {
             (Self::LogicBit(l0), Self::LogicBit(r0)) => l0.read().unwrap().get_name() == r0.read().unwrap().get_name(),
             (Self::LogicGroup(l0), Self::LogicGroup(r0)) => l0.read().unwrap().get_name() == r0.read().unwrap().get_name(),
             (Self::LogicUnion(l0), Self::LogicUnion(r0)) => l0.read().unwrap().get_name() == r0.read().unwrap().get_name(),
+            (Self::LogicTypeRef(l0), Self::LogicTypeRef(r0)) => l0 == r0,
+            (Self::Array(l0), Self::Array(r0)) => l0 == r0,
 
             _ => core::mem::discriminant(self) == core::mem::discriminant(other),
         }
@@ -141,6 +151,7 @@ impl TypeIndication {
             
             TypedValue::Streamlet(_) => TypeIndication::AnyStreamlet,
             TypedValue::Port(_) => TypeIndication::AnyPort,
+            TypedValue::PortBundle(_) => TypeIndication::AnyPortBundle,
 
             TypedValue::Implementation(_) => TypeIndication::AnyImplementation,
             TypedValue::Instance(_) => TypeIndication::AnyInstance,
@@ -149,16 +160,19 @@ impl TypeIndication {
             TypedValue::If(_) => TypeIndication::ComplierBuiltin,
             TypedValue::For(_) => TypeIndication::ComplierBuiltin,
 
+            //the element type is only known if all elements agree, e.g. [1, 2.0] is an array of any
             TypedValue::Array(v) => {
-                if v.len() == 0 {
-                    TypeIndication::Array(Box::new(TypeIndication::Any))
-                }
-                else {
-                    TypeIndication::Array(Box::new(Self::infer_from_typed_value(&v[0]))) //maybe we should have TypeIndication::Array?
+                let element_types: Vec<TypeIndication> = v.iter().map(|element| Self::infer_from_typed_value(element)).collect();
+                match element_types.first() {
+                    Some(first_type) if element_types.iter().all(|element_type| element_type == first_type) => TypeIndication::Array(Box::new(first_type.clone())),
+                    _ => TypeIndication::Array(Box::new(TypeIndication::Any)),
                 }
             },
 
+            TypedValue::Map(_) => TypeIndication::Map,
+
             TypedValue::Function(_) => TypeIndication::Function,
+            TypedValue::FunctionDefinition(_) => TypeIndication::ComplierBuiltin,
 
             //TypedValue during evaluation phase only
             TypedValue::RefToVar(var) => Self::infer_from_typed_value(&var.read().unwrap().get_value()),
@@ -168,6 +182,13 @@ impl TypeIndication {
     }
 
     pub fn is_compatible_with_typed_value(&self, value: &TypedValue) -> bool {
+        if let TypedValue::RefToVar(var) = value {
+            return self.is_compatible_with_typed_value(&var.read().unwrap().get_value());
+        }
+        let logic_type = match value {
+            TypedValue::LogicTypeValue(logic_type) => Some(logic_type.read().unwrap().clone()),
+            _ => None,
+        };
         match self {
             TypeIndication::Any => { true },
             TypeIndication::Unknown => { false },   // we'd be striect here
@@ -204,6 +225,10 @@ impl TypeIndication {
                 TypedValue::Port(_) => true,
                 _ => false,
             },
+            TypeIndication::AnyPortBundle => match value {
+                TypedValue::PortBundle(_) => true,
+                _ => false,
+            },
             TypeIndication::AnyImplementation => match value {
                 TypedValue::Implementation(_) => true,
                 _ => false,
@@ -216,8 +241,31 @@ impl TypeIndication {
                 TypedValue::Net(_) => true,
                 _ => false,
             },
+            TypeIndication::Map => match value {
+                TypedValue::Map(_) => true,
+                _ => false,
+            },
+            TypeIndication::PackageReference => match value {
+                TypedValue::PackageReferenceValue(_) => true,
+                _ => false,
+            },
+            TypeIndication::Function => match value {
+                TypedValue::Function(_) | TypedValue::FunctionDefinition(_) => true,
+                _ => false,
+            },
+
+            //a logic type indicator only requires the same kind of logic type, e.g. any Bit for Bit(8)
+            TypeIndication::LogicNull => matches!(logic_type, Some(LogicType::LogicNullType)),
+            TypeIndication::LogicStream(_) => matches!(logic_type, Some(LogicType::LogicStreamType(_))),
+            TypeIndication::LogicBit(_) => matches!(logic_type, Some(LogicType::LogicBitType(_))),
+            TypeIndication::LogicGroup(_) => matches!(logic_type, Some(LogicType::LogicGroupType(_))),
+            TypeIndication::LogicUnion(_) => matches!(logic_type, Some(LogicType::LogicUnionType(_))),
+            TypeIndication::LogicTypeRef(_) => logic_type.is_some(),
 
-            _ => todo!()
+            TypeIndication::Array(element_type) => match value {
+                TypedValue::Array(elements) => elements.iter().all(|element| element_type.is_compatible_with_typed_value(element)),
+                _ => false,
+            },
         }
     }
 }
@@ -239,6 +287,7 @@ pub enum TypedValue {
 
     Streamlet(Arc<RwLock<Streamlet>>),
     Port(Arc<RwLock<Port>>),
+    PortBundle(Arc<RwLock<PortBundle>>),
 
     Implementation(Arc<RwLock<Implementation>>),
     Instance(Arc<RwLock<Instance>>),
@@ -248,8 +297,11 @@ pub enum TypedValue {
     For(Arc<RwLock<For>>),
 
     Array(Vec<TypedValue>),
+    //the keys are sorted so that the iteration order doesn't depend on the declaration order
+    Map(BTreeMap<String, TypedValue>),
 
     Function(Arc<RwLock<Function>>),
+    FunctionDefinition(Arc<RwLock<FunctionDefinition>>),
 
     //special TypedValue during evaluation
     RefToVar(Arc<RwLock<Variable>>),
@@ -288,6 +340,7 @@ impl DeepClone for TypedValue {
                 TypedValue::Streamlet(output)
             },
             TypedValue::Port(v) => TypedValue::Port(v.deep_clone()),
+            TypedValue::PortBundle(v) => TypedValue::PortBundle(v.deep_clone()),
             TypedValue::Implementation(v) => {
                 //update the parent implementation for nets
                 let output = v.deep_clone();
@@ -309,9 +362,11 @@ impl DeepClone for TypedValue {
             TypedValue::If(v) => TypedValue::If(v.deep_clone()),
             TypedValue::For(v) => TypedValue::For(v.deep_clone()),
             TypedValue::Array(v) => TypedValue::Array(v.deep_clone()),
+            TypedValue::Map(v) => TypedValue::Map(v.iter().map(|(key, value)| (key.clone(), value.deep_clone())).collect()),
             TypedValue::RefToVar(v) => TypedValue::RefToVar(v.deep_clone()),
             TypedValue::Identifier(v) => TypedValue::Identifier(v.deep_clone()),
             TypedValue::Function(v) => TypedValue::Function(v.deep_clone()),
+            TypedValue::FunctionDefinition(v) => TypedValue::FunctionDefinition(v.deep_clone()),
         };
         return output;
     }
@@ -357,6 +412,10 @@ impl Serialize for TypedValue {
                 let v = v.read().unwrap();
                 state.serialize_field("value", &*v)?;
             },
+            TypedValue::PortBundle(v) => {
+                let v = v.read().unwrap();
+                state.serialize_field("value", &*v)?;
+            },
             TypedValue::Implementation(v) => {
                 let v = v.read().unwrap();
                 state.serialize_field("value", &*v)?;
@@ -380,10 +439,17 @@ impl Serialize for TypedValue {
             TypedValue::Array(v) => {
                 state.serialize_field("value", &*v)?;
             },
+            TypedValue::Map(v) => {
+                state.serialize_field("value", &*v)?;
+            },
             TypedValue::Function(v) => {
                 let v = v.read().unwrap();
                 state.serialize_field("value", &*v)?;
             },
+            TypedValue::FunctionDefinition(v) => {
+                let v = v.read().unwrap();
+                state.serialize_field("value", &*v)?;
+            },
 
             //TypedValue during evaluation phase only
             TypedValue::RefToVar(v) => {
@@ -420,6 +486,7 @@ impl std::cmp::PartialEq for TypedValue {
                 }
                 return true;
             },
+            (Self::Map(v0), Self::Map(v1)) => v0 == v1,
             _ => core::mem::discriminant(self) == core::mem::discriminant(other),
         }
     }
@@ -439,6 +506,7 @@ impl TypedValue {
             TypedValue::LogicTypeValue(logic_type) => return logic_type.read().unwrap().get_brief_info(),
             TypedValue::Streamlet(streamlet) => return streamlet.read().unwrap().get_brief_info(),
             TypedValue::Port(port) => return port.read().unwrap().get_name(),
+            TypedValue::PortBundle(bundle) => return bundle.read().unwrap().get_brief_info(),
             TypedValue::Implementation(implementation) => return implementation.read().unwrap().get_brief_info(),
             TypedValue::Instance(inst) => {
                 let parent_impl = inst.read().unwrap().get_derived_impl();
@@ -452,7 +520,9 @@ impl TypedValue {
             TypedValue::If(_) => todo!(),
             TypedValue::For(_) => todo!(),
             TypedValue::Array(array) => return format!("Array({})", array.iter().map(|i| i.get_brief_info()).collect::<Vec<_>>().join(", ")),
+            TypedValue::Map(map) => return format!("Map({})", map.iter().map(|(key, value)| format!("{}: {}", key, value.get_brief_info())).collect::<Vec<_>>().join(", ")),
             TypedValue::Function(v) => return format!("Fcuntion:{}({})", v.read().unwrap().get_function_id(), v.read().unwrap().get_function_arg_exps().iter().map(|(_key, value)| value.clone()).collect::<Vec<_>>().join(" ,")),
+            TypedValue::FunctionDefinition(v) => return format!("FunctionDefinition({})", v.read().unwrap().get_name()),
             TypedValue::RefToVar(v) => return format!("RefToVar({})", v.read().unwrap().get_name()),
             TypedValue::Identifier(v) => return format!("Identifier({})", v.read().unwrap().get_brief_info()),
         }
@@ -5,7 +5,7 @@ use serde::ser::SerializeSeq;
 use serde::{Serialize, Serializer, Deserialize};
 
 use crate::deep_clone::{DeepClone, DeepClone_ArcLock};
-use crate::tydi_memory_representation::{TypedValue, TypeIndication, CodeLocation, TraitCodeLocationAccess, Streamlet, LogicType, Port, Implementation, Instance};
+use crate::tydi_memory_representation::{TypedValue, TypeIndication, CodeLocation, TraitCodeLocationAccess, Streamlet, LogicType, Port, PortBundle, Implementation, Instance};
 use crate::trait_common::GetName;
 use crate::{generate_get_pub, generate_access_pub, generate_set_pub, generate_access, generate_set, generate_get, generate_name};
 
@@ -303,6 +303,27 @@ impl Variable {
         return Arc::new(RwLock::new(output));
     }
 
+    pub fn new_port_bundle(name: String, port_bundle: Arc<RwLock<PortBundle>>) -> Arc<RwLock<Self>> {
+        let typed_value = TypedValue::PortBundle(port_bundle);
+        let output = Self {
+            name: name,
+            exp: None,
+            evaluated: EvaluationStatus::NotEvaluated,
+            value: typed_value,
+            array_size: None,
+            type_indication: TypeIndication::AnyPortBundle,
+            is_property_of_scope: false,
+            declare_location: CodeLocation::new_unknown(),
+            parent_scope: None,
+            id_in_scope: None,
+            is_name_user_defined: false,
+            template_args: None,
+            template_arg_values: None,
+            alias: vec![],
+        };
+        return Arc::new(RwLock::new(output));
+    }
+
     pub fn new_implementation(name: String, implementation: Arc<RwLock<Implementation>>) -> Arc<RwLock<Self>> {
         let typed_value = TypedValue::Implementation(implementation);
         let output = Self {
@@ -82,14 +82,3 @@ macro_rules! generate_set_in_arc_rwlock {
         }
     };
 }
-
-pub fn generate_random_str(length: usize) -> String {
-    use rand::{thread_rng, Rng};
-    use rand::distributions::Alphanumeric;
-    let rand_string: String = thread_rng()
-            .sample_iter(&Alphanumeric)
-            .take(length)
-            .map(char::from)
-            .collect();
-    return rand_string;
-}